@@ -31,14 +31,14 @@ fn main() {
 
     let result: std::thread::Result<()> = std::panic::catch_unwind(|| {
         let mut static_lake: Void<Lake<1024>> = FBC!(Lake::<SIZE>::new());
-        static_lake.mark();
+        let start = static_lake.mark();
         assert_eq!(static_lake.offset(), 0);
 
         let mut droplet: Droplet<16, Lake<1024>> =
             unsafe { (*static_lake.0).alloc::<16>().unwrap() };
         assert_eq!(static_lake.offset(), 16);
 
-        static_lake.reset_to_mark(); // reset lake to mark
+        static_lake.reset_to_mark(start).unwrap(); // reset lake to mark
         assert_eq!(static_lake.offset(), 0);
         droplet.d_as_mut_slice(); // now this is outlived droplet and this method should panic
         println!("Leaked lake with FBC! was used and rewound correctly");
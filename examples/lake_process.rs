@@ -45,7 +45,7 @@ fn example_3() {
         // Silly closure: ignore limit, return way too much
         vec![42u8; remaining + 1]
     });
-    assert!(matches!(result, Err(LakeError::Overflow)));
+    assert!(matches!(result, Err(LakeError::Overflow { .. })));
 }
 
 fn example_4() {
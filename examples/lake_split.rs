@@ -46,11 +46,11 @@ fn example_process_in_view() {
 fn example_mark_reset_view() {
     let mut lake: Lake<1024> = Lake::new();
     let mut view: LakeView<1024> = lake.split(256).unwrap();
-    view.mark(); // Save offset = 0
+    let start = view.mark(); // Save offset = 0
     let _ = view.alloc::<64>().unwrap();
     let _ = view.alloc::<32>().unwrap();
     assert_eq!(view.used(), 96);
-    view.reset_to_mark(); // back to 0
+    view.reset_to_mark(start).unwrap(); // back to 0
     assert_eq!(view.used(), 0);
 }
 
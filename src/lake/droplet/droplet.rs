@@ -1,8 +1,8 @@
 use crate::{
     guard,
     lake::{
-        droplet::{DropletBase, DropletDeserializeExt},
-        LakeMeta,
+        droplet::{check_fits, DropletBase, DropletDeserializeExt},
+        LakeError, LakeMeta,
     },
 };
 use std::{
@@ -13,8 +13,8 @@ use std::{
 /// A `Droplet` is a fixed-size memory fragment allocated from a `Lake`.
 ///
 /// It retains a pointer to the allocated data and maintains a link back to the lake it came from.
-/// This link allows it to verify its validity (`generation`, `offset`) and, if it's the most recent allocation,
-/// automatically rewind the lake's offset on drop.
+/// This link allows it to verify its validity (`generation`, `offset`). Wrapped in a `ScopedDroplet`
+/// (see `alloc_scoped`), it also rewinds the lake's offset on drop if it's the most recent allocation.
 ///
 /// Think of it as a persistent memory "droplet" carved out of a larger "lake" —
 /// it doesn’t own memory, but lives temporarily inside it with context-aware lifecycle management.
@@ -25,7 +25,10 @@ use std::{
 #[repr(C)]
 pub struct Droplet<const N: usize, TARGET: LakeMeta> {
     pub(crate) ptr: NonNull<[u8; N]>,
+    /// Lake offset right after this droplet. It stays valid while the lake doesn't rewind below it.
     pub(crate) offset: usize,
+    /// Write cursor used by `DropletBase` helpers.
+    pub(crate) cursor: usize,
     pub(crate) lake: *mut TARGET,
     pub(crate) generation: usize,
 }
@@ -38,6 +41,7 @@ impl<const N: usize, TARGET: LakeMeta> Clone for Droplet<N, TARGET> {
         Self {
             ptr: self.ptr,
             offset: self.offset,
+            cursor: self.cursor,
             lake: self.lake,
             generation: self.generation,
         }
@@ -69,8 +73,26 @@ impl<TARGET: LakeMeta, const N: usize> Droplet<N, TARGET> {
     /// Check whether the droplet is still valid.
     /// A tiny bit of sanity-checking in the middle of a memory free-for-all.
     pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+    /// Same check as `is_valid`, but tells you what went wrong.
+    pub fn validate(&self) -> Result<(), LakeError> {
         let lake: &dyn LakeMeta = unsafe { self.get_lake() };
-        lake.generation() == self.generation && lake.offset() >= self.offset
+        if lake.generation() != self.generation {
+            return Err(LakeError::StaleGeneration {
+                expected: self.generation,
+                found: lake.generation(),
+            });
+        }
+        if lake.offset() < self.offset {
+            return Err(LakeError::Rewound {
+                offset: self.offset,
+                current: lake.offset(),
+            });
+        }
+        #[cfg(feature = "sanitizer")]
+        lake.check_red_zone(self.offset);
+        Ok(())
     }
     pub fn generation(&self) -> usize {
         self.generation
@@ -105,11 +127,11 @@ impl<const N: usize, T: LakeMeta> DropletBase for Droplet<N, T> {
     }
 
     fn d_offset_mut(&mut self) -> &mut usize {
-        &mut self.offset
+        &mut self.cursor
     }
 
     fn d_offset(&self) -> usize {
-        self.offset
+        self.cursor
     }
 }
 
@@ -149,4 +171,12 @@ impl<const N: usize, TARGET: LakeMeta> DropletDeserializeExt for Droplet<N, TARG
         let count: usize = N / size;
         unsafe { Some(core::slice::from_raw_parts(ptr, count)) }
     }
+
+    #[inline(always)]
+    fn try_deserialize<T>(&self) -> Result<&T, LakeError> {
+        self.validate()?;
+        let ptr: *const u8 = self.ptr.as_ptr() as *const u8;
+        check_fits::<T>(ptr, N)?;
+        Ok(unsafe { &*(ptr as *const T) })
+    }
 }
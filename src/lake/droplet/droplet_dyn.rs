@@ -1,28 +1,36 @@
-use crate::lake::droplet::DropletDeserializeExt;
+use crate::lake::droplet::{check_fits, DropletDeserializeExt};
 use crate::{
     guard,
-    lake::{droplet::DropletBase, LakeMeta},
+    lake::{droplet::DropletBase, LakeError, LakeMeta},
 };
 use std::ptr::NonNull;
 use crate::droplet::Droplet;
 
+/// `SIZE` of droplets whose lake capacity is only known at runtime.
+pub const DYN_SIZE: usize = 0;
+
 /// A dynamically sized `Droplet` carved from a `Lake`.
 ///
 /// Unlike the fixed-size variant, this one can hold any slice up to `SIZE`,
 /// making it ideal for data generated at runtime (e.g. JSON, HTTP bodies).
 ///
 /// The droplet keeps a raw pointer back to its lake (yes, Rust, we're adults),
-/// enabling generation/offset-based validity checks and automatic rewind on drop.
+/// enabling generation/offset-based validity checks — and, via `alloc_dyn_scoped`, automatic rewind on drop.
 ///
 /// It's not a smart pointer — it's a clever pointer.
+///
+/// Droplets from runtime-sized lakes (`DynLake`) have no `SIZE` to carry and use the
+/// default, so they show up in signatures as plain `DropletDyn`.
 #[must_use]
-pub struct DropletDyn<const SIZE: usize> {
+pub struct DropletDyn<const SIZE: usize = DYN_SIZE> {
     /// Pointer to the beginning of the data (may not be aligned).
     pub ptr: NonNull<u8>,
     /// Actual length of the slice in use (≤ SIZE).
     pub len: usize,
-    /// Offset into the lake buffer.
+    /// Lake offset right after this droplet. It stays valid while the lake doesn't rewind below it.
     pub(crate) offset: usize,
+    /// Write cursor used by `DropletBase` helpers.
+    pub(crate) cursor: usize,
     /// Raw link back to the lake (don’t tell borrow checker).
     pub(crate) lake: *mut dyn LakeMeta,
     /// Generation to guard against stale reuse.
@@ -36,6 +44,7 @@ impl<const SIZE: usize> Clone for DropletDyn<SIZE>
             ptr: self.ptr,
             len: self.len,
             offset: self.offset,
+            cursor: self.cursor,
             lake: self.lake,
             generation: self.generation
         }
@@ -65,10 +74,10 @@ impl<const SIZE: usize> DropletBase for DropletDyn<SIZE> {
         unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
     }
     fn d_offset_mut(&mut self) -> &mut usize {
-        &mut self.offset
+        &mut self.cursor
     }
     fn d_offset(&self) -> usize {
-        self.offset
+        self.cursor
     }
 }
 
@@ -100,8 +109,26 @@ impl<const SIZE: usize> DropletDyn<SIZE> {
     /// - Same generation
     /// - Not yet overwritten in the lake
     pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+    /// Same check as `is_valid`, but tells you what went wrong.
+    pub fn validate(&self) -> Result<(), LakeError> {
         let lake: &dyn LakeMeta = unsafe { self.get_lake() };
-        lake.generation() == self.generation && lake.offset() >= self.offset
+        if lake.generation() != self.generation {
+            return Err(LakeError::StaleGeneration {
+                expected: self.generation,
+                found: lake.generation(),
+            });
+        }
+        if lake.offset() < self.offset {
+            return Err(LakeError::Rewound {
+                offset: self.offset,
+                current: lake.offset(),
+            });
+        }
+        #[cfg(feature = "sanitizer")]
+        lake.check_red_zone(self.offset);
+        Ok(())
     }
 }
 
@@ -125,4 +152,12 @@ impl<const SIZE: usize> DropletDeserializeExt for DropletDyn<SIZE> {
         let count = self.d_len() / size;
         unsafe { Some(core::slice::from_raw_parts(ptr, count)) }
     }
+
+    #[inline(always)]
+    fn try_deserialize<T>(&self) -> Result<&T, LakeError> {
+        self.validate()?;
+        let ptr: *const u8 = self.ptr.as_ptr() as *const u8;
+        check_fits::<T>(ptr, self.len)?;
+        Ok(unsafe { &*(ptr as *const T) })
+    }
 }
@@ -0,0 +1,243 @@
+use crate::lake::droplet::{check_fits, DropletBase, DropletDeserializeExt};
+use crate::lake::{LakeAllocatorExt, LakeError};
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+/// A fixed-size droplet that borrows the lake instead of pointing at it.
+///
+/// `Droplet` keeps a raw pointer back to its lake and checks `generation` at runtime;
+/// nothing stops it from outliving the lake or crossing threads while the lake is being reset.
+/// `DropletRef` is the safe sibling: it holds the lake's `&'lake mut` borrow, so the borrow
+/// checker refuses to `reset`, `rewind` or drop the lake while the droplet is around.
+/// No generation checks needed — the compiler already did them.
+///
+/// Get one with `LakeAllocatorExt::alloc_ref`. That keeps the `&mut` borrow it took, so only
+/// one lives at a time; `LakeAllocatorExt::ref_scope` hands out as many as fit:
+///
+/// ```
+/// use lake::{lake::LakeAllocatorExt, Lake};
+///
+/// let mut lake: Lake<64> = Lake::new();
+/// let scope = lake.ref_scope();
+/// let mut header = scope.alloc_ref::<8>().unwrap();
+/// let mut body = scope.alloc_dyn_ref(16).unwrap();
+/// header.fill(1);
+/// body.fill(2);
+/// assert_eq!(lake.as_slice()[7..9], [1, 2]);
+/// ```
+///
+/// ```compile_fail
+/// use lake::{lake::LakeAllocatorExt, Lake};
+///
+/// let mut lake: Lake<64> = Lake::new();
+/// let droplet = lake.alloc_ref::<8>().unwrap();
+/// lake.reset(); // error: `lake` is still borrowed by `droplet`
+/// droplet[0];
+/// ```
+#[must_use]
+pub struct DropletRef<'lake, const N: usize> {
+    pub(crate) data: &'lake mut [u8; N],
+    /// Write cursor used by `DropletBase` helpers.
+    pub(crate) offset: usize,
+}
+
+/// A runtime-sized droplet that borrows the lake. See `DropletRef`.
+///
+/// Get one with `LakeAllocatorExt::alloc_dyn_ref`.
+#[must_use]
+pub struct DropletDynRef<'lake> {
+    pub(crate) data: &'lake mut [u8],
+    /// Write cursor used by `DropletBase` helpers.
+    pub(crate) offset: usize,
+}
+
+/// Hands out any number of `DropletRef`s and `DropletDynRef`s from one lake.
+///
+/// `alloc_ref` needs `&mut self`, so its droplet keeps the lake to itself. The scope takes that
+/// borrow once, for `'lake`, and carves through `&self` instead: every droplet gets fresh water
+/// above the last one, and none of them can outlive the borrow. The scope never rewinds, so the
+/// droplets stay disjoint; once they are all gone the lake is yours again.
+///
+/// Get one with `LakeAllocatorExt::ref_scope`.
+///
+/// ```compile_fail
+/// use lake::{lake::LakeAllocatorExt, Lake};
+///
+/// let mut lake: Lake<64> = Lake::new();
+/// let droplet = lake.ref_scope().alloc_ref::<8>().unwrap();
+/// lake.reset(); // error: `lake` is still borrowed by `droplet`
+/// droplet[0];
+/// ```
+pub struct DropletRefScope<'lake, L: LakeAllocatorExt> {
+    lake: NonNull<L>,
+    _marker: PhantomData<&'lake mut L>,
+}
+
+impl<'lake, L: LakeAllocatorExt> DropletRefScope<'lake, L> {
+    /// Borrow the lake for as long as the scope or any droplet from it is alive.
+    #[inline(always)]
+    pub fn new(lake: &'lake mut L) -> Self {
+        Self {
+            lake: NonNull::from(lake),
+            _marker: PhantomData,
+        }
+    }
+    /// The lake behind the scope. Never escapes the scope's own methods.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn lake(&self) -> &mut L {
+        &mut *self.lake.as_ptr()
+    }
+    /// Borrow-checked `alloc`, any number of times.
+    #[inline(always)]
+    pub fn alloc_ref<const N: usize>(&self) -> Option<DropletRef<'lake, N>> {
+        let data: *mut [u8; N] = unsafe { self.lake() }.try_alloc_struct::<[u8; N]>().ok()?;
+        // SAFETY: fresh water above everything handed out so far, and the lake stays borrowed for 'lake.
+        Some(DropletRef::new(unsafe { &mut *data }))
+    }
+    /// Borrow-checked `alloc_dyn`, any number of times.
+    #[inline(always)]
+    pub fn alloc_dyn_ref(&self, size: usize) -> Option<DropletDynRef<'lake>> {
+        let data: *mut [u8] = unsafe { self.lake() }.try_alloc_slice::<u8>(size).ok()?;
+        Some(DropletDynRef::new(unsafe { &mut *data }))
+    }
+}
+
+impl<'lake, const N: usize> DropletRef<'lake, N> {
+    #[inline(always)]
+    pub(crate) fn new(data: &'lake mut [u8; N]) -> Self {
+        Self { data, offset: 0 }
+    }
+    /// Give up the droplet and keep the bytes for as long as the lake is borrowed.
+    #[inline(always)]
+    pub fn into_mut(self) -> &'lake mut [u8; N] {
+        self.data
+    }
+}
+
+impl<'lake> DropletDynRef<'lake> {
+    #[inline(always)]
+    pub(crate) fn new(data: &'lake mut [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+    /// Give up the droplet and keep the bytes for as long as the lake is borrowed.
+    #[inline(always)]
+    pub fn into_mut(self) -> &'lake mut [u8] {
+        self.data
+    }
+}
+
+impl<const N: usize> DropletBase for DropletRef<'_, N> {
+    #[inline(always)]
+    fn d_as_ptr(&self) -> *const u8 {
+        self.data.as_ptr()
+    }
+    #[inline(always)]
+    fn d_as_mut_ptr(&mut self) -> *mut u8 {
+        self.data.as_mut_ptr()
+    }
+    #[inline(always)]
+    fn d_len(&self) -> usize {
+        N
+    }
+    #[inline(always)]
+    fn d_as_mut_slice(&mut self) -> &mut [u8] {
+        self.data
+    }
+    fn d_offset_mut(&mut self) -> &mut usize {
+        &mut self.offset
+    }
+    fn d_offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl DropletBase for DropletDynRef<'_> {
+    #[inline(always)]
+    fn d_as_ptr(&self) -> *const u8 {
+        self.data.as_ptr()
+    }
+    #[inline(always)]
+    fn d_as_mut_ptr(&mut self) -> *mut u8 {
+        self.data.as_mut_ptr()
+    }
+    #[inline(always)]
+    fn d_len(&self) -> usize {
+        self.data.len()
+    }
+    #[inline(always)]
+    fn d_as_mut_slice(&mut self) -> &mut [u8] {
+        self.data
+    }
+    fn d_offset_mut(&mut self) -> &mut usize {
+        &mut self.offset
+    }
+    fn d_offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<const N: usize> Deref for DropletRef<'_, N> {
+    type Target = [u8; N];
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.data
+    }
+}
+
+impl<const N: usize> DerefMut for DropletRef<'_, N> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.data
+    }
+}
+
+impl Deref for DropletDynRef<'_> {
+    type Target = [u8];
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.data
+    }
+}
+
+impl DerefMut for DropletDynRef<'_> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.data
+    }
+}
+
+impl<const N: usize> DropletDeserializeExt for DropletRef<'_, N> {
+    #[inline(always)]
+    fn deserialize<T: Copy>(&self) -> Option<&T> {
+        self.try_deserialize().ok()
+    }
+    #[inline(always)]
+    fn deserialize_slice<T: Copy>(&self) -> Option<&[T]> {
+        self.d_as_slice_of()
+    }
+    #[inline(always)]
+    fn try_deserialize<T: Copy>(&self) -> Result<&T, LakeError> {
+        check_fits::<T>(self.data.as_ptr(), N)?;
+        Ok(unsafe { &*(self.data.as_ptr() as *const T) })
+    }
+}
+
+impl DropletDeserializeExt for DropletDynRef<'_> {
+    #[inline(always)]
+    fn deserialize<T: Copy>(&self) -> Option<&T> {
+        self.try_deserialize().ok()
+    }
+    #[inline(always)]
+    fn deserialize_slice<T: Copy>(&self) -> Option<&[T]> {
+        self.d_as_slice_of()
+    }
+    #[inline(always)]
+    fn try_deserialize<T: Copy>(&self) -> Result<&T, LakeError> {
+        check_fits::<T>(self.data.as_ptr(), self.data.len())?;
+        Ok(unsafe { &*(self.data.as_ptr() as *const T) })
+    }
+}
@@ -1,13 +1,40 @@
 use std::backtrace::Backtrace;
 use crate::lake::memory::LakeTools;
+use crate::lake::LakeError;
 use std::ops::Add;
 
 pub mod droplet;
 pub mod droplet_dyn;
+pub mod droplet_ref;
+pub mod scoped;
+mod write;
 
 pub trait DropletDeserializeExt {
     fn deserialize<T: Copy>(&self) -> Option<&T>;
     fn deserialize_slice<T: Copy>(&self) -> Option<&[T]>;
+    /// Checked `deserialize`: the droplet must still be valid, large enough and aligned for `T`.
+    fn try_deserialize<T: Copy>(&self) -> Result<&T, LakeError>;
+}
+
+/// Can `len` bytes at `ptr` hold a `T`?
+#[inline(always)]
+pub(crate) fn check_fits<T>(ptr: *const u8, len: usize) -> Result<(), LakeError> {
+    let (size, align) = (size_of::<T>(), align_of::<T>());
+    if size > len {
+        return Err(LakeError::Overflow {
+            requested: size,
+            align,
+            remaining: len,
+            capacity: len,
+        });
+    }
+    if ptr.align_offset(align) != 0 {
+        return Err(LakeError::Misaligned {
+            address: ptr as usize,
+            align,
+        });
+    }
+    Ok(())
 }
 
 pub trait DropletBase {
@@ -21,7 +48,7 @@ pub trait DropletBase {
         *self.d_offset_mut() = 0;
     }
     fn d_remaining(&self) -> usize {
-        self.d_len() - self.d_offset()
+        self.d_len().saturating_sub(self.d_offset())
     }
     #[inline(always)]
     fn d_as_slice(&self) -> &[u8] {
@@ -64,6 +91,18 @@ pub trait DropletBase {
         *self.d_offset_mut() += len;
     }
 
+    /// Write as much of `src` as still fits, returning how many bytes went in.
+    /// Backs the `io::Write` / `fmt::Write` impls, which never panic on overflow.
+    #[inline(always)]
+    fn d_write_partial(&mut self, src: &[u8]) -> usize {
+        // `d_remaining` saturates: a cursor at or past the end takes nothing.
+        let len: usize = src.len().min(self.d_remaining());
+        if len > 0 {
+            self.d_write(src.as_ptr(), len);
+        }
+        len
+    }
+
     #[inline(always)]
     fn write_num_str(&mut self, mut value: usize) {
         let mut tmp: [u8; 20] = [0u8; 20];
@@ -82,11 +121,14 @@ pub trait DropletBase {
         }
         let len: usize = tmp.len() - curr;
         unsafe { self.d_write(tmp.as_ptr().add(curr), len) };
-        *self.d_offset_mut() += len;
     }
 
     #[inline(always)]
     fn write_num_str_fixed(&mut self, mut value: usize, len: usize) {
+        #[cfg(feature = "sanitizer")]
+        if len > self.d_remaining() {
+            panic!("Droplet overflow: trying to write {len} digits, but only {} bytes remain\n{}", self.d_remaining(), Backtrace::capture());
+        }
         unsafe {
             let dst: *mut u8 = self.d_as_mut_ptr().add(self.d_offset() + len);
             let mut ptr: *mut u8 = dst;
@@ -100,6 +142,10 @@ pub trait DropletBase {
     }
     #[inline(always)]
     fn write_byte(&mut self, c: u8) {
+        #[cfg(feature = "sanitizer")]
+        if self.d_remaining() == 0 {
+            panic!("Droplet overflow: trying to write a byte into a full droplet\n{}", Backtrace::capture());
+        }
         unsafe {
             *self.d_as_mut_ptr().add(self.d_offset()) = c;
             *self.d_offset_mut() += 1;
@@ -110,8 +156,8 @@ pub trait DropletBase {
 #[macro_export]
 macro_rules! guard {
     ($self:ident) => {
-        if !$self.is_valid() {
-            panic!("Droplet is outlive generation or no longer valid");
+        if let Err(err) = $self.validate() {
+            panic!("Droplet is outlive generation or no longer valid: {err}");
         }
     };
 }
@@ -0,0 +1,90 @@
+use crate::lake::LakeMeta;
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+/// A droplet that gives its water back when it goes out of scope.
+///
+/// Plain droplets are `Clone` and forget about the lake once they are dropped.
+/// A `ScopedDroplet` remembers exactly which stretch of the lake it took, and on drop —
+/// if it is still the top-most allocation of the same generation — rewinds the lake's
+/// offset with `LakeMeta::set_offset`. Hot loops get stack-like reuse for free,
+/// no `mark`/`reset_to_mark` bookkeeping required.
+///
+/// It holds the lake's `&mut` borrow for `'lake`, so nothing else can allocate on top of it,
+/// reset the lake or drop it while the droplet is alive. `into_inner` gives the borrow back.
+///
+/// ```compile_fail
+/// use lake::Lake;
+///
+/// let scoped = {
+///     let mut lake: Lake<64> = Lake::new();
+///     lake.alloc_scoped::<8>().unwrap() // error: `lake` does not live long enough
+/// };
+/// drop(scoped);
+/// ```
+#[must_use]
+pub struct ScopedDroplet<'lake, D> {
+    pub(crate) droplet: D,
+    pub(crate) lake: *mut dyn LakeMeta,
+    /// Borrow marker – makes sure we don't outlive the lake we rewind.
+    pub(crate) _marker: PhantomData<&'lake mut dyn LakeMeta>,
+    /// Lake offset before the allocation.
+    pub(crate) start: usize,
+    /// Lake offset right after the allocation.
+    pub(crate) end: usize,
+    pub(crate) generation: usize,
+}
+
+impl<D> ScopedDroplet<'_, D> {
+    /// Wrap a droplet that was just carved from `lake`, whose offset was `start` before it.
+    #[inline(always)]
+    pub(crate) fn new(droplet: D, lake: *mut dyn LakeMeta, start: usize) -> Self {
+        let meta: &dyn LakeMeta = unsafe { &*lake };
+        Self {
+            droplet,
+            lake,
+            _marker: PhantomData,
+            start,
+            end: meta.offset(),
+            generation: meta.generation(),
+        }
+    }
+    /// Is this still the last thing in the lake?
+    #[inline(always)]
+    pub fn is_top(&self) -> bool {
+        let lake: &dyn LakeMeta = unsafe { &*self.lake };
+        lake.generation() == self.generation && lake.offset() == self.end
+    }
+    /// Keep the memory: unwrap the droplet and never rewind.
+    #[inline(always)]
+    pub fn into_inner(self) -> D {
+        let this = std::mem::ManuallyDrop::new(self);
+        unsafe { std::ptr::read(&this.droplet) }
+    }
+}
+
+impl<D> Deref for ScopedDroplet<'_, D> {
+    type Target = D;
+    #[inline(always)]
+    fn deref(&self) -> &D {
+        &self.droplet
+    }
+}
+
+impl<D> DerefMut for ScopedDroplet<'_, D> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut D {
+        &mut self.droplet
+    }
+}
+
+impl<D> Drop for ScopedDroplet<'_, D> {
+    fn drop(&mut self) {
+        if self.is_top() {
+            let lake: &mut dyn LakeMeta = unsafe { &mut *self.lake };
+            lake.set_offset(self.start);
+        }
+    }
+}
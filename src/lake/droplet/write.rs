@@ -0,0 +1,49 @@
+use crate::lake::droplet::{
+    droplet::Droplet,
+    droplet_dyn::DropletDyn,
+    droplet_ref::{DropletDynRef, DropletRef},
+    DropletBase,
+};
+use crate::lake::LakeMeta;
+use std::{fmt, io};
+
+/// `io::Write` and `fmt::Write` on top of `DropletBase`: bytes land at the droplet's write
+/// cursor, and running out of room is an error (`WriteZero` / `fmt::Error`), never a panic.
+/// `fmt::Write` is all-or-nothing per string, so a failed `write!` leaves no half-written piece.
+macro_rules! droplet_write {
+    ($([$($generics:tt)*] $ty:ty),+ $(,)?) => {
+        $(
+            impl<$($generics)*> io::Write for $ty {
+                #[inline(always)]
+                fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                    match self.d_write_partial(buf) {
+                        0 if !buf.is_empty() => Err(io::Error::new(io::ErrorKind::WriteZero, "droplet is full")),
+                        written => Ok(written),
+                    }
+                }
+                #[inline(always)]
+                fn flush(&mut self) -> io::Result<()> {
+                    Ok(())
+                }
+            }
+
+            impl<$($generics)*> fmt::Write for $ty {
+                #[inline(always)]
+                fn write_str(&mut self, s: &str) -> fmt::Result {
+                    if s.len() > self.d_remaining() {
+                        return Err(fmt::Error);
+                    }
+                    self.d_write_partial(s.as_bytes());
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+droplet_write! {
+    [const N: usize, T: LakeMeta] Droplet<N, T>,
+    [const SIZE: usize] DropletDyn<SIZE>,
+    [const N: usize] DropletRef<'_, N>,
+    [] DropletDynRef<'_>,
+}
@@ -0,0 +1,143 @@
+use crate::lake::lake::dyn_lake::DynLake;
+use crate::lake::lake::lake::Lake;
+use crate::lake::lake::lake_view::LakeView;
+use crate::lake::utils::align_up;
+use crate::lake::LakeBuffer;
+use allocator_api2::alloc::{AllocError, Allocator, Layout};
+use std::{marker::PhantomData, ptr::NonNull};
+
+/// A copyable `Allocator` handle to a lake.
+///
+/// `Allocator` works through `&self`, while a lake needs `&mut self` to move its offset.
+/// The handle bridges the two: it holds the lake's exclusive borrow for `'a`, and every copy
+/// of it bumps the same offset. That lets `Vec<T, LakeHandle>`, `Box<T, LakeHandle>` or a
+/// `hashbrown` map live inside the lake — and once they're gone, `reset()` wipes them all at once.
+///
+/// Deallocation only reclaims memory when it was the most recent allocation (stack-like);
+/// everything else is simply left in the water until the next `reset`.
+pub struct LakeHandle<'a, L: LakeBuffer> {
+    lake: NonNull<L>,
+    _marker: PhantomData<&'a mut L>,
+}
+
+impl<L: LakeBuffer> Clone for LakeHandle<'_, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L: LakeBuffer> Copy for LakeHandle<'_, L> {}
+
+impl<'a, L: LakeBuffer> LakeHandle<'a, L> {
+    /// Borrow the lake for as long as the handle (or any of its copies) is alive.
+    #[inline(always)]
+    pub fn new(lake: &'a mut L) -> Self {
+        Self {
+            lake: NonNull::from(lake),
+            _marker: PhantomData,
+        }
+    }
+    /// The lake behind the handle. Never escapes the handle's own methods.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn lake(&self) -> &mut L {
+        &mut *self.lake.as_ptr()
+    }
+    /// Is `ptr..ptr + size` the last thing carved from the lake?
+    #[inline(always)]
+    unsafe fn is_top(&self, ptr: NonNull<u8>, size: usize) -> bool {
+        let lake: &mut L = self.lake();
+        ptr.as_ptr().add(size) == lake.buf_ptr().add(lake.offset())
+    }
+}
+
+unsafe impl<L: LakeBuffer> Allocator for LakeHandle<'_, L> {
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let lake: &mut L = unsafe { self.lake() };
+        let base: usize = lake.buf_ptr() as usize;
+        let start: usize = align_up(base + lake.offset(), layout.align()) - base;
+        let end: usize = start.checked_add(layout.size()).ok_or(AllocError)?;
+        if end > lake.capacity() {
+            return Err(AllocError);
+        }
+        lake.set_offset(end);
+        let ptr: NonNull<u8> = unsafe { NonNull::new_unchecked(lake.buf_ptr().add(start)) };
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if self.is_top(ptr, layout.size()) {
+            let lake: &mut L = self.lake();
+            let offset: usize = ptr.as_ptr() as usize - lake.buf_ptr() as usize;
+            lake.set_offset(offset);
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        // Top of the lake and alignment still holds: just let the water rise.
+        if self.is_top(ptr, old_layout.size()) && ptr.as_ptr().align_offset(new_layout.align()) == 0 {
+            let lake: &mut L = self.lake();
+            let start: usize = ptr.as_ptr() as usize - lake.buf_ptr() as usize;
+            let end: usize = start.checked_add(new_layout.size()).ok_or(AllocError)?;
+            if end <= lake.capacity() {
+                lake.set_offset(end);
+                return Ok(NonNull::slice_from_raw_parts(ptr, new_layout.size()));
+            }
+        }
+        let new: NonNull<[u8]> = self.allocate(new_layout)?;
+        std::ptr::copy_nonoverlapping(ptr.as_ptr(), new.as_ptr() as *mut u8, old_layout.size());
+        Ok(new)
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        if ptr.as_ptr().align_offset(new_layout.align()) != 0 {
+            let new: NonNull<[u8]> = self.allocate(new_layout)?;
+            std::ptr::copy_nonoverlapping(ptr.as_ptr(), new.as_ptr() as *mut u8, new_layout.size());
+            return Ok(new);
+        }
+        if self.is_top(ptr, old_layout.size()) {
+            let lake: &mut L = self.lake();
+            let start: usize = ptr.as_ptr() as usize - lake.buf_ptr() as usize;
+            lake.set_offset(start + new_layout.size());
+        }
+        Ok(NonNull::slice_from_raw_parts(ptr, new_layout.size()))
+    }
+}
+
+impl<const SIZE: usize> Lake<SIZE> {
+    /// Hand out an `Allocator` for collections that should live inside this lake.
+    #[inline(always)]
+    pub fn allocator(&mut self) -> LakeHandle<'_, Self> {
+        LakeHandle::new(self)
+    }
+}
+
+impl<'v, const SIZE: usize> LakeView<'v, SIZE> {
+    /// Hand out an `Allocator` for collections that should live inside this view.
+    #[inline(always)]
+    pub fn allocator(&mut self) -> LakeHandle<'_, Self> {
+        LakeHandle::new(self)
+    }
+}
+
+impl DynLake {
+    /// Hand out an `Allocator` for collections that should live inside this lake.
+    #[inline(always)]
+    pub fn allocator(&mut self) -> LakeHandle<'_, Self> {
+        LakeHandle::new(self)
+    }
+}
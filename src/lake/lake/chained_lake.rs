@@ -0,0 +1,508 @@
+use crate::lake::droplet::droplet::Droplet;
+use crate::lake::droplet::droplet_dyn::DropletDyn;
+use crate::lake::droplet::scoped::ScopedDroplet;
+use crate::lake::lake::lake::DynWater;
+use crate::lake::lake::lake_view::LakeView;
+use crate::lake::lake::drop_list::DropList;
+use crate::lake::lake::mark::{MarkStack, MarkToken, Marks};
+use crate::lake::lake::raw_lake::process_in_place_into;
+use crate::lake::utils::align_up;
+use crate::lake::{slice_bytes, LakeAlloc, LakeAllocatorExt, LakeError, LakeId, LakeMeta, LakeSnapshot};
+use std::{convert::Infallible, error::Error, marker::PhantomData, ptr::NonNull};
+
+/// How a `ChainedLake` sizes the next chunk once the current one runs dry.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LakeGrowth {
+    /// Every new chunk is twice as large as the previous one.
+    Double,
+    /// Every new chunk has the same fixed size.
+    Step(usize),
+}
+
+/// One link of the chain: a boxed buffer and its position in the logical offset space.
+#[derive(Clone)]
+pub(super) struct Chunk {
+    pub(super) buf: DynWater,
+    pub(super) base: usize,
+}
+
+impl Chunk {
+    #[inline(always)]
+    fn new(base: usize, len: usize) -> Self {
+        Self {
+            buf: DynWater::new(len),
+            base,
+        }
+    }
+    /// Like `new`, but gives up instead of aborting when the system is out of memory.
+    #[inline(always)]
+    fn try_new(base: usize, len: usize) -> Option<Self> {
+        Some(Self {
+            buf: DynWater::try_new(len)?,
+            base,
+        })
+    }
+    #[inline(always)]
+    fn end(&self) -> usize {
+        self.base + self.buf.len()
+    }
+}
+
+/// A `Lake` that refuses to overflow: when the current chunk is exhausted, a new one is linked in.
+///
+/// All chunks share a single logical offset space — chunk `i` starts where chunk `i - 1` ends —
+/// so the offset stays a plain `usize` and everything built on it keeps working across chunk boundaries:
+/// - `snapshot`/`rewind` and `mark`/`reset_to_mark` simply move the logical offset back.
+/// - Droplets keep their generation and offset guards, exactly like in `Lake`.
+/// - An allocation never straddles two chunks; the unused tail of a chunk is skipped.
+///
+/// Chunks are kept after `reset`/`rewind` and reused, so a lake that once absorbed a
+/// long-tail payload does not hit the allocator again for the next one. Use `shrink` to release them.
+///
+/// `SIZE` is the size of the first chunk.
+#[derive(Clone)]
+pub struct ChainedLake<const SIZE: usize> {
+    /// Destructors owed by tracked values. Declared first, so they run while the buffer is still around.
+    pub(super) drops: DropList,
+    /// The chain itself. Boxed chunks never move, so droplets stay put while the chain grows.
+    pub(super) chunks: Vec<Chunk>,
+    /// Logical offset across all chunks.
+    pub(super) offset: usize,
+    /// Stack of marks for scoped rewinds.
+    pub(super) mark_stack: MarkStack,
+    /// Generation counter to guard against stale droplets.
+    pub(super) generation: usize,
+    /// Who this is, for `origin`. Fresh for every lake and every clone.
+    pub(super) id: LakeId,
+    /// Wipe data with 0u8 while reset
+    pub(super) zeroing: bool,
+    /// Sizing policy for new chunks.
+    pub(super) growth: LakeGrowth,
+}
+
+impl<const SIZE: usize> ChainedLake<SIZE> {
+    /// Create a new chained lake with a single `SIZE` chunk that doubles on growth.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::with_growth(LakeGrowth::Double)
+    }
+    /// Create a new chained lake with a custom growth policy.
+    #[inline(always)]
+    pub fn with_growth(growth: LakeGrowth) -> Self {
+        Self {
+            chunks: vec![Chunk::new(0, SIZE)],
+            offset: 0,
+            mark_stack: MarkStack::with_capacity(100),
+            drops: DropList::default(),
+            generation: 0,
+            id: LakeId::default(),
+            zeroing: false,
+            growth,
+        }
+    }
+    /// Index of the chunk the logical offset currently points into.
+    #[inline(always)]
+    fn chunk_index(&self, offset: usize) -> usize {
+        self.chunks.partition_point(|chunk| chunk.base <= offset).saturating_sub(1)
+    }
+    /// Address of logical `offset`, inside the chunk that holds it.
+    #[inline(always)]
+    fn chunk_ptr(&mut self, offset: usize) -> *mut u8 {
+        let idx: usize = self.chunk_index(offset);
+        let chunk: &mut Chunk = &mut self.chunks[idx];
+        unsafe { chunk.buf.as_mut_ptr().add(offset - chunk.base) }
+    }
+    /// Size of the chunk that would be linked after the last one.
+    #[inline(always)]
+    fn next_chunk_len(&self, needed: usize) -> usize {
+        let last: usize = self.chunks.last().map_or(SIZE, |chunk| chunk.buf.len());
+        let len: usize = match self.growth {
+            LakeGrowth::Double => last.saturating_mul(2),
+            LakeGrowth::Step(step) => step,
+        };
+        len.max(needed).max(1)
+    }
+    /// Find room for `size` bytes aligned to `align`, linking a new chunk if needed.
+    /// Moves the offset past the reservation and returns its start address and logical offset.
+    /// Fails only when the new chunk can't be obtained from the system.
+    #[inline(always)]
+    fn try_reserve(&mut self, size: usize, align: usize) -> Result<(*mut u8, usize), LakeError> {
+        let overflow = |lake: &Self| LakeError::Overflow {
+            requested: size,
+            align,
+            remaining: lake.chunk_remaining(),
+            capacity: lake.capacity(),
+        };
+        let mut idx: usize = self.chunk_index(self.offset);
+        let mut from: usize = self.offset;
+        loop {
+            if idx == self.chunks.len() {
+                let base: usize = self.capacity();
+                let needed: usize = size.checked_add(align - 1).ok_or_else(|| overflow(self))?;
+                let len: usize = self.next_chunk_len(needed);
+                let chunk: Chunk = Chunk::try_new(base, len).ok_or_else(|| overflow(self))?;
+                self.chunks.push(chunk);
+            }
+            let chunk: &mut Chunk = &mut self.chunks[idx];
+            let (start, base, len): (*mut u8, usize, usize) = (chunk.buf.as_mut_ptr(), chunk.base, chunk.buf.len());
+            let local: usize = from.max(base) - base;
+            let aligned: usize = align_up(start as usize + local, align) - start as usize;
+            // A malformed length must not wrap around into a "fitting" reservation.
+            let end: usize = aligned.checked_add(size).ok_or_else(|| overflow(self))?;
+            if end <= len {
+                let offset: usize = base + aligned;
+                self.offset = base + end;
+                return Ok((unsafe { start.add(aligned) }, offset));
+            }
+            idx += 1;
+            from = 0;
+        }
+    }
+    /// Take a snapshot of the lake's current water level.
+    #[inline(always)]
+    pub fn snapshot(&self) -> LakeSnapshot {
+        LakeMeta::snapshot(self)
+    }
+    /// Rewind to a previous snapshot, no matter how many chunks were linked since.
+    #[inline(always)]
+    pub fn rewind(&mut self, snapshot: LakeSnapshot) {
+        LakeMeta::rewind(self, snapshot)
+    }
+    /// Like `rewind`, but a snapshot from another lake, an older generation or above
+    /// the current water level comes back as an error instead of a panic.
+    #[inline(always)]
+    pub fn try_rewind(&mut self, snapshot: LakeSnapshot) -> Result<(), LakeError> {
+        LakeMeta::try_rewind(self, snapshot)
+    }
+    /// Split off a `LakeView`. The view always lives inside a single chunk.
+    ///
+    /// The view borrows the lake, since `reset` + `shrink` could free the chunk under it:
+    ///
+    /// ```compile_fail
+    /// use lake::ChainedLake;
+    ///
+    /// let mut lake: ChainedLake<64> = ChainedLake::new();
+    /// let mut view = lake.split(4096).unwrap();
+    /// lake.reset();
+    /// lake.shrink(); // error: `lake` is still borrowed by `view`
+    /// view.alloc::<8>();
+    /// ```
+    #[inline(always)]
+    pub fn split(&mut self, len: usize) -> Result<LakeView<'_, SIZE>, LakeError> {
+        let (buf, _) = self.try_reserve(len, 1)?;
+        Ok(LakeView {
+            buf,
+            capacity: len,
+            offset: 0,
+            mark_stack: MarkStack::default(),
+            _marker: PhantomData,
+            generation: 0,
+            id: LakeId::default(),
+            zeroing: self.zeroing,
+            secure: false,
+            high_water: 0,
+        })
+    }
+    /// Invoke a closure to produce data into the lake, returning a dynamic droplet.
+    /// The closure is told how much room is left in the current chunk; if it returns
+    /// more than that, the data is moved into a freshly linked chunk instead of overflowing.
+    #[inline(always)]
+    pub fn process<F>(&mut self, f: F) -> Result<DropletDyn<SIZE>, LakeError>
+    where
+        F: FnOnce(usize) -> Vec<u8>,
+    {
+        self.try_process(|remaining| Ok::<_, Infallible>(f(remaining)))
+    }
+    /// Like `process`, but the closure may fail. Its error comes back as `LakeError::Process`
+    /// and nothing is written into the lake.
+    #[inline(always)]
+    pub fn try_process<F, E>(&mut self, f: F) -> Result<DropletDyn<SIZE>, LakeError>
+    where
+        F: FnOnce(usize) -> Result<Vec<u8>, E>,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let data: Vec<u8> = f(self.chunk_remaining()).map_err(|err| LakeError::Process(err.into()))?;
+        let len: usize = data.len();
+        let generation: usize = self.generation;
+        let (dst, _) = self.try_reserve(len, 1)?;
+
+        unsafe {
+            std::ptr::copy_nonoverlapping(data.as_ptr(), dst, len);
+        }
+
+        let lake: *mut dyn LakeMeta = self as *mut Self as *mut dyn LakeMeta;
+
+        Ok(DropletDyn {
+            ptr: unsafe { NonNull::new_unchecked(dst) },
+            len,
+            offset: self.offset,
+            cursor: 0,
+            lake,
+            generation,
+        })
+    }
+    /// Zero-copy `process`: the closure writes straight into the lake's free water and returns
+    /// how many bytes it produced. Only those bytes are committed; on error nothing is.
+    #[inline(always)]
+    pub fn process_in_place<F, E>(&mut self, f: F) -> Result<DropletDyn<SIZE>, LakeError>
+    where
+        F: FnOnce(&mut [u8]) -> Result<usize, E>,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        // A brim-full chunk has no free tail to lend: link the next one first.
+        if self.chunk_remaining() == 0 {
+            let (_, start) = self.try_reserve(1, 1)?;
+            self.offset = start;
+        }
+        let offset: usize = self.offset;
+        let generation: usize = self.generation;
+        let dst: *mut u8 = self.chunk_ptr(offset);
+        let len: usize = process_in_place_into(dst, self.chunk_remaining(), self.capacity(), f)?;
+
+        self.offset += len;
+
+        let lake: *mut dyn LakeMeta = self as *mut Self as *mut dyn LakeMeta;
+
+        Ok(DropletDyn {
+            ptr: unsafe { NonNull::new_unchecked(dst) },
+            len,
+            offset: self.offset,
+            cursor: 0,
+            lake,
+            generation,
+        })
+    }
+    /// Allocate a fixed-size droplet, growing the chain if the current chunk is too small.
+    #[inline(always)]
+    pub fn alloc<const N: usize>(&mut self) -> Option<Droplet<N, ChainedLake<SIZE>>> {
+        let (ptr, _) = self.try_reserve(N, 1).ok()?;
+        Some(Droplet {
+            ptr: NonNull::new(ptr as *mut [u8; N])?,
+            offset: self.offset,
+            cursor: 0,
+            lake: self as *mut Self,
+            generation: self.generation,
+        })
+    }
+    #[inline(always)]
+    pub fn alloc_dyn(&mut self, size: usize) -> Option<DropletDyn<SIZE>> {
+        let (dst, _) = self.try_reserve(size, 1).ok()?;
+        let lake: *mut dyn LakeMeta = self as *mut Self as *mut dyn LakeMeta;
+        Some(DropletDyn {
+            ptr: unsafe { NonNull::new_unchecked(dst) },
+            len: size,
+            offset: self.offset,
+            cursor: 0,
+            lake,
+            generation: self.generation,
+        })
+    }
+    /// Like `alloc`, but the droplet rewinds the lake when dropped, if it is still on top.
+    #[inline(always)]
+    pub fn alloc_scoped<const N: usize>(&mut self) -> Option<ScopedDroplet<'_, Droplet<N, ChainedLake<SIZE>>>> {
+        let start: usize = self.offset;
+        let droplet = self.alloc::<N>()?;
+        let lake: *mut dyn LakeMeta = self as *mut Self as *mut dyn LakeMeta;
+        Some(ScopedDroplet::new(droplet, lake, start))
+    }
+    /// Like `alloc_dyn`, but the droplet rewinds the lake when dropped, if it is still on top.
+    #[inline(always)]
+    pub fn alloc_dyn_scoped(&mut self, size: usize) -> Option<ScopedDroplet<'_, DropletDyn<SIZE>>> {
+        let start: usize = self.offset;
+        let droplet = self.alloc_dyn(size)?;
+        let lake: *mut dyn LakeMeta = self as *mut Self as *mut dyn LakeMeta;
+        Some(ScopedDroplet::new(droplet, lake, start))
+    }
+    /// Wipe the lake clean and start a new generation. Linked chunks are kept for reuse.
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.drops.release_all();
+        if self.zeroing {
+            let offset: usize = self.offset;
+            for chunk in self.chunks.iter_mut().take_while(|chunk| chunk.base < offset) {
+                let touched: usize = (offset - chunk.base).min(chunk.buf.len());
+                chunk.buf[..touched].fill(0);
+            }
+        }
+        self.offset = 0;
+        self.mark_stack.clear();
+        self.generation += 1;
+    }
+    /// Release every chunk that lies entirely beyond the current offset (the first one always stays).
+    #[inline(always)]
+    pub fn shrink(&mut self) {
+        let keep: usize = self.chunk_index(self.offset) + 1;
+        self.chunks.truncate(keep);
+    }
+    /// Returns used capacity (logical offset, skipped chunk tails included).
+    #[inline(always)]
+    pub fn used(&self) -> usize {
+        self.offset
+    }
+    /// Returns remaining capacity across all linked chunks.
+    #[inline(always)]
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.offset
+    }
+    /// Returns room left in the chunk the offset currently points into.
+    #[inline(always)]
+    pub fn chunk_remaining(&self) -> usize {
+        self.chunks[self.chunk_index(self.offset)].end().saturating_sub(self.offset)
+    }
+    /// Look at the next `N` bytes of the current chunk without allocating them.
+    /// `None` if they would spill into the next chunk.
+    #[inline(always)]
+    pub fn peek<const N: usize>(&self) -> Option<&[u8; N]> {
+        let chunk: &Chunk = &self.chunks[self.chunk_index(self.offset)];
+        let local: usize = self.offset - chunk.base;
+        chunk.buf.get(local..local.checked_add(N)?)?.try_into().ok()
+    }
+    /// The allocated part of the current chunk. Earlier chunks don't sit next to it in memory;
+    /// reach their bytes through their droplets.
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[u8] {
+        let chunk: &Chunk = &self.chunks[self.chunk_index(self.offset)];
+        &chunk.buf[..(self.offset - chunk.base).min(chunk.buf.len())]
+    }
+    /// Number of chunks currently linked.
+    #[inline(always)]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.offset == 0
+    }
+    #[inline(always)]
+    pub fn get_offset(&self) -> usize {
+        self.used()
+    }
+    /// Mark the current position (you can come back to it later).
+    /// Keep the token: `reset_to_mark` needs it to find this exact mark again.
+    #[inline(always)]
+    pub fn mark(&mut self) -> MarkToken {
+        self.push_mark(None)
+    }
+    /// `mark`, with a name that shows up when the token is debug-printed.
+    #[inline(always)]
+    pub fn mark_named(&mut self, name: &'static str) -> MarkToken {
+        self.push_mark(Some(name))
+    }
+    /// Roll back to the mark behind `token`, popping it and every mark pushed after it.
+    /// Fails, and leaves everything as is, if the mark is gone, from an older generation,
+    /// or above the current water level.
+    #[inline(always)]
+    pub fn reset_to_mark(&mut self, token: MarkToken) -> Result<(), LakeError> {
+        self.pop_mark(token)
+    }
+    /// Move the most recent mark to the current offset.
+    #[inline(always)]
+    pub fn move_mark(&mut self) {
+        self.move_top_mark();
+    }
+    /// Number of marks on the stack.
+    #[inline(always)]
+    pub fn marks(&self) -> usize {
+        self.mark_stack.len()
+    }
+    /// Wipe everything. Same as `reset`, but sounds more decisive.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.reset();
+    }
+    /// Total capacity of all linked chunks.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.chunks.last().map_or(0, Chunk::end)
+    }
+    #[inline(always)]
+    pub fn growth(&self) -> LakeGrowth {
+        self.growth
+    }
+    #[inline(always)]
+    pub fn set_zeroing(&mut self, state: bool) {
+        self.zeroing = state;
+    }
+    #[inline(always)]
+    pub fn get_zeroing(&mut self) -> bool {
+        self.zeroing
+    }
+}
+
+impl<const SIZE: usize> Default for ChainedLake<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> LakeAllocatorExt for ChainedLake<N> {
+    fn try_alloc_struct<T>(&mut self) -> Result<&mut T, LakeError> {
+        let (ptr, _) = self.try_reserve(size_of::<T>(), align_of::<T>())?;
+        Ok(unsafe { &mut *(ptr as *mut T) })
+    }
+
+    fn try_alloc_slice<T>(&mut self, count: usize) -> Result<&mut [T], LakeError> {
+        let size: usize = slice_bytes::<T>(count)?;
+        let (ptr, _) = self.try_reserve(size, align_of::<T>())?;
+        Ok(unsafe { core::slice::from_raw_parts_mut(ptr as *mut T, count) })
+    }
+}
+
+impl<const N: usize> LakeMeta for ChainedLake<N> {
+    fn offset(&self) -> usize {
+        self.offset
+    }
+    fn origin(&self) -> usize {
+        self.id.get()
+    }
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+    fn offset_mut(&mut self) -> &mut usize {
+        &mut self.offset
+    }
+    fn generation(&self) -> usize {
+        self.generation
+    }
+    fn drops(&mut self) -> Option<&mut DropList> {
+        Some(&mut self.drops)
+    }
+    fn mark_stack(&mut self) -> Option<&mut MarkStack> {
+        Some(&mut self.mark_stack)
+    }
+    fn generation_mut(&mut self) -> Option<&mut usize> {
+        Some(&mut self.generation)
+    }
+}
+
+impl<const N: usize> Marks for ChainedLake<N> {
+    #[inline(always)]
+    fn marks_mut(&mut self) -> &mut MarkStack {
+        &mut self.mark_stack
+    }
+}
+
+// Inherent methods win name lookup, so these forward instead of recursing.
+impl<const SIZE: usize> LakeAlloc for ChainedLake<SIZE> {
+    type DynDroplet = DropletDyn<SIZE>;
+    #[inline(always)]
+    fn alloc<const N: usize>(&mut self) -> Option<Droplet<N, Self>> {
+        self.alloc::<N>()
+    }
+    #[inline(always)]
+    fn alloc_dyn(&mut self, size: usize) -> Option<DropletDyn<SIZE>> {
+        self.alloc_dyn(size)
+    }
+    #[inline(always)]
+    fn peek<const N: usize>(&self) -> Option<&[u8; N]> {
+        self.peek::<N>()
+    }
+    #[inline(always)]
+    fn as_slice(&self) -> &[u8] {
+        self.as_slice()
+    }
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.reset()
+    }
+}
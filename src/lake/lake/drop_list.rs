@@ -0,0 +1,78 @@
+/// One value living in the lake that still owes us a `Drop`.
+struct DropEntry {
+    /// Offset the value starts at. Rewinding to it (or below) lets the value go.
+    offset: usize,
+    ptr: *mut u8,
+    drop: unsafe fn(*mut u8),
+}
+
+/// Destructors of values placed with `alloc_tracked`, in allocation order.
+///
+/// Plain allocations are just bytes — the lake forgets them when the water level drops.
+/// Tracked ones (a `String`, a `Vec`, an `Arc`) are remembered here and dropped in LIFO
+/// order as soon as `reset`, `rewind`, `reset_to_mark` or a rolled-back sandbox moves
+/// the offset to or below where they start. Whatever is still tracked when the list
+/// itself goes away is dropped too.
+///
+/// Cloning a lake does not clone its drop-list: the copied bytes would otherwise be
+/// dropped twice. Tracked values in a clone are simply forgotten.
+#[derive(Default)]
+pub struct DropList {
+    entries: Vec<DropEntry>,
+}
+
+impl DropList {
+    /// Remember to drop the `T` at `ptr` once the lake falls below `offset`.
+    /// Types without drop glue are not recorded at all.
+    #[inline(always)]
+    pub(crate) fn push<T>(&mut self, offset: usize, ptr: *mut T) {
+        unsafe fn drop_in_place<T>(ptr: *mut u8) {
+            std::ptr::drop_in_place(ptr as *mut T);
+        }
+        if std::mem::needs_drop::<T>() {
+            self.entries.push(DropEntry {
+                offset,
+                ptr: ptr as *mut u8,
+                drop: drop_in_place::<T>,
+            });
+        }
+    }
+    /// Drop every tracked value starting at or above `offset`, newest first.
+    #[inline(always)]
+    pub(crate) fn release(&mut self, offset: usize) {
+        while self.entries.last().is_some_and(|entry| entry.offset >= offset) {
+            let entry: DropEntry = self.entries.pop().unwrap();
+            unsafe { (entry.drop)(entry.ptr) };
+        }
+    }
+    /// Drop everything still tracked.
+    #[inline(always)]
+    pub(crate) fn release_all(&mut self) {
+        self.release(0);
+    }
+    /// Number of values still waiting for their `Drop`.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Clone for DropList {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl Drop for DropList {
+    fn drop(&mut self) {
+        self.release_all();
+    }
+}
+
+// Only `Send` values are ever tracked (see `alloc_tracked`), and the list never hands them out.
+unsafe impl Send for DropList {}
+unsafe impl Sync for DropList {}
@@ -0,0 +1,34 @@
+use crate::lake::droplet::droplet_dyn::DropletDyn;
+use crate::lake::lake::lake::DynWater;
+use crate::lake::lake::raw_lake::{raw_lake_api, RawLake};
+
+/// A `Lake` whose capacity is chosen at runtime instead of baked into the type.
+///
+/// Same still waters, same rules — linear allocation, snapshots, marks, generations —
+/// but the reservoir is a boxed slice sized from config, not a `const SIZE`.
+/// Droplets carved from it don't carry the lake's size either:
+/// fixed droplets are `Droplet<N, DynLake>` and dynamic ones are plain `DropletDyn`.
+///
+/// Handy for per-tenant arenas, where every tenant gets its own size and
+/// nobody wants to monomorphise the whole pipeline per size.
+#[derive(Clone)]
+pub struct DynLake {
+    /// Our "water reservoir" – preallocated, boxed for stable address, aligned to `LAKE_ALIGN` – and all its bookkeeping.
+    pub(super) raw: RawLake<DynWater>,
+}
+
+impl DynLake {
+    /// Create a new, pristine lake holding `capacity` bytes.
+    #[inline(always)]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            raw: RawLake::new(Box::new(DynWater::new(capacity))),
+        }
+    }
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.raw.capacity()
+    }
+}
+
+raw_lake_api!([] DynLake, DropletDyn);
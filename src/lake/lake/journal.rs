@@ -0,0 +1,113 @@
+use crate::lake::memory::secure_zero;
+use crate::lake::{LakeBuffer, LakeError};
+
+/// Original bytes of a region the sandbox wrote over, so rollback can put them back.
+struct JournalEntry {
+    offset: usize,
+    bytes: Vec<u8>,
+}
+
+// The saved bytes may be secrets from a secure lake: wipe them whether they were
+// restored (rollback) or thrown away (commit).
+impl Drop for JournalEntry {
+    fn drop(&mut self) {
+        secure_zero(&mut self.bytes);
+    }
+}
+
+/// A sandbox that also remembers what it overwrote. Opened by `LakeSandboxExt::journaled_sandbox`.
+///
+/// A plain `SandboxGuard` only lowers the water level on rollback; bytes patched below it stay
+/// patched. Write through `write`/`region_mut` here instead and every committed byte you touch
+/// is journaled first. Dropping the guard without `commit` restores them — newest first — and then
+/// rewinds the offset like any sandbox.
+///
+/// Writes that bypass the guard (older droplets, `as_mut_slice()`) are not journaled.
+#[must_use]
+pub struct JournalGuard<'a, T: LakeBuffer> {
+    lake: Option<&'a mut T>,
+    base_offset: usize,
+    journal: Vec<JournalEntry>,
+    committed: bool,
+}
+
+impl<'a, T: LakeBuffer> JournalGuard<'a, T> {
+    #[inline(always)]
+    pub(crate) fn new(lake: &'a mut T) -> Self {
+        Self {
+            base_offset: lake.offset(),
+            lake: Some(lake),
+            journal: Vec::new(),
+            committed: false,
+        }
+    }
+    #[inline(always)]
+    pub fn view(&mut self) -> &mut T {
+        self.lake.as_mut().unwrap()
+    }
+    /// Water level the sandbox rolls back to.
+    #[inline(always)]
+    pub fn base_offset(&self) -> usize {
+        self.base_offset
+    }
+    /// Bytes saved for rollback so far.
+    #[inline(always)]
+    pub fn journaled(&self) -> usize {
+        self.journal.iter().map(|entry| entry.bytes.len()).sum()
+    }
+    /// Borrow `len` allocated bytes at `offset` for writing. The part below `base_offset`
+    /// is journaled before you get it; the part above is the sandbox's own water anyway.
+    pub fn region_mut(&mut self, offset: usize, len: usize) -> Result<&mut [u8], LakeError> {
+        let lake: &mut T = self.lake.as_mut().unwrap();
+        let used: usize = lake.offset();
+        let end: usize = match offset.checked_add(len) {
+            Some(end) if end <= used => end,
+            _ => return Err(LakeError::OutOfBounds { offset, len, used }),
+        };
+        let base: *mut u8 = lake.buf_ptr();
+        let committed_end: usize = end.min(self.base_offset);
+        if offset < committed_end {
+            let old: &[u8] =
+                unsafe { std::slice::from_raw_parts(base.add(offset), committed_end - offset) };
+            self.journal.push(JournalEntry {
+                offset,
+                bytes: old.to_vec(),
+            });
+        }
+        Ok(unsafe { std::slice::from_raw_parts_mut(base.add(offset), end - offset) })
+    }
+    /// Copy `bytes` into the lake at `offset`, journaling whatever they overwrite.
+    #[inline(always)]
+    pub fn write(&mut self, offset: usize, bytes: &[u8]) -> Result<(), LakeError> {
+        self.region_mut(offset, bytes.len())?.copy_from_slice(bytes);
+        Ok(())
+    }
+    /// Keep the new allocations and the patched bytes.
+    #[inline(always)]
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+    /// Commit and get back the original lake.
+    #[inline(always)]
+    pub fn commit_and_return(mut self) -> &'a mut T {
+        self.committed = true;
+        self.lake.take().unwrap()
+    }
+}
+
+impl<T: LakeBuffer> Drop for JournalGuard<'_, T> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        if let Some(lake) = self.lake.as_mut() {
+            let base: *mut u8 = lake.buf_ptr();
+            for entry in self.journal.drain(..).rev() {
+                unsafe {
+                    std::ptr::copy_nonoverlapping(entry.bytes.as_ptr(), base.add(entry.offset), entry.bytes.len());
+                }
+            }
+            lake.set_offset(self.base_offset);
+        }
+    }
+}
@@ -1,6 +1,10 @@
-use crate::lake::{droplet::{droplet::Droplet, droplet_dyn::DropletDyn}, LakeAllocatorExt, LakeError, LakeMeta};
-use std::{marker::PhantomData, ptr::NonNull};
+use crate::lake::{droplet::{droplet::Droplet, droplet_dyn::DropletDyn, scoped::ScopedDroplet}, slice_bytes, LakeAlloc, LakeAllocatorExt, LakeBuffer, LakeError, LakeId, LakeMeta, LakeSnapshot};
+use std::{alloc::Layout, convert::Infallible, error::Error, marker::PhantomData, ptr::NonNull};
+use crate::lake::lake::mark::{MarkStack, MarkToken, Marks};
+use crate::lake::lake::raw_lake::{process_in_place_into, process_into};
 use crate::lake::utils::align_up;
+use crate::lake::memory::secure_zero;
+use crate::force_static;
 
 /// A view into a section of the lake — a *temporary tributary* or shallow basin
 /// that lives within the larger memory lake but has its own offset and capacity.
@@ -19,13 +23,19 @@ pub struct LakeView<'a, const SIZE: usize> {
     /// How deep we’ve gone into this section.
     pub(super) offset: usize,
     /// Stack of memory marks for scoped rewinding.
-    pub(super) mark_stack: Vec<usize>,
+    pub(super) mark_stack: MarkStack,
     /// Borrow marker – makes sure we don't outlive the parent lake.
     pub(super) _marker: PhantomData<&'a mut [u8]>,
     /// Generation counter to detect expired droplets.
     pub(super) generation: usize,
+    /// Who this is, for `origin`. A sub-view is a lake of its own, even at offset 0.
+    pub(super) id: LakeId,
 
     pub(super) zeroing: bool,
+    /// Wipe with `secure_zero` on every rollback. Inherited from a secure parent.
+    pub(super) secure: bool,
+    /// Highest water level seen since the last secure wipe. Nothing above it needs wiping.
+    pub(super) high_water: usize,
 }
 
 impl<'a, const SIZE: usize> LakeView<'a, SIZE> {
@@ -38,17 +48,74 @@ impl<'a, const SIZE: usize> LakeView<'a, SIZE> {
             buf: buf.as_mut_ptr(),
             capacity: buf.len(),
             offset: 0,
-            mark_stack: Vec::new(),
+            mark_stack: MarkStack::default(),
             _marker: PhantomData,
             generation: 0,
+            id: LakeId::default(),
             zeroing: false,
+            secure: false,
+            high_water: 0,
+        }
+    }
+    /// Would `len` more bytes still fit inside this view? Never looks past `capacity`,
+    /// whatever the parent lake's `SIZE` is.
+    #[inline(always)]
+    fn fits(&self, len: usize) -> bool {
+        len <= self.capacity - self.offset
+    }
+    /// Take a snapshot of the view's current water level.
+    #[inline(always)]
+    pub fn snapshot(&self) -> LakeSnapshot {
+        LakeMeta::snapshot(self)
+    }
+    /// Rewind to a previous snapshot (rollback to known-safe state).
+    #[inline(always)]
+    pub fn rewind(&mut self, snapshot: LakeSnapshot) {
+        LakeMeta::rewind(self, snapshot)
+    }
+    /// Like `rewind`, but a snapshot from another lake, an older generation or above
+    /// the current water level comes back as an error instead of a panic.
+    #[inline(always)]
+    pub fn try_rewind(&mut self, snapshot: LakeSnapshot) -> Result<(), LakeError> {
+        LakeMeta::try_rewind(self, snapshot)
+    }
+    /// Move the water level to `offset`, the same way `RawLake::lower_to` does:
+    /// in secure mode everything between `offset` and the high-water mark is wiped first.
+    #[inline(always)]
+    fn lower_to(&mut self, offset: usize) {
+        self.high_water = self.high_water.max(self.offset);
+        if self.secure {
+            if self.high_water > offset {
+                secure_zero(unsafe { std::slice::from_raw_parts_mut(self.buf.add(offset), self.high_water - offset) });
+            }
+            self.high_water = offset;
+        }
+        self.offset = offset;
+    }
+    /// Carve `size` bytes aligned to `align`, or explain why the lake can't.
+    #[inline(always)]
+    fn try_bump(&mut self, size: usize, align: usize) -> Result<*mut u8, LakeError> {
+        // A view starts wherever its parent left off, so align the address, not the offset.
+        let base: usize = self.buf as usize;
+        let offset: usize = align_up(base + self.offset, align) - base;
+        match offset.checked_add(size) {
+            Some(end) if end <= self.capacity => {
+                self.offset = end;
+                Ok(unsafe { self.buf.add(offset) })
+            }
+            _ => Err(LakeError::Overflow {
+                requested: size,
+                align,
+                remaining: self.remaining(),
+                capacity: self.capacity(),
+            }),
         }
     }
     /// Allocate a fixed-size droplet from this view. Just like in `Lake`,
     /// but bounded by the view’s own capacity.
     #[inline(always)]
     pub fn alloc<const N: usize>(&mut self) -> Option<Droplet<N, LakeView<'a, SIZE>>> {
-        if self.offset + N > self.capacity {
+        if !self.fits(N) {
             return None;
         }
         let ptr: *mut [u8; N] = unsafe { self.buf.add(self.offset) as *mut [u8; N] };
@@ -56,12 +123,68 @@ impl<'a, const SIZE: usize> LakeView<'a, SIZE> {
         let droplet = Droplet {
             ptr: NonNull::new(ptr)?,
             offset: self.offset + N,
+            cursor: 0,
             lake: self as *mut Self,
             generation: self.generation,
         };
         self.offset += N;
         Some(droplet)
     }
+    /// Like `alloc`, but the droplet rewinds the lake when dropped, if it is still on top.
+    #[inline(always)]
+    pub fn alloc_scoped<const N: usize>(&mut self) -> Option<ScopedDroplet<'_, Droplet<N, LakeView<'a, SIZE>>>> {
+        let start: usize = self.offset;
+        let droplet = self.alloc::<N>()?;
+        let lake: *mut dyn LakeMeta = force_static!(self as *mut Self as *mut (dyn LakeMeta + 'a));
+        Some(ScopedDroplet::new(droplet, lake, start))
+    }
+    /// Allocate a runtime-sized droplet from this view, bounded by the view's own capacity.
+    #[inline(always)]
+    pub fn alloc_dyn(&mut self, size: usize) -> Option<DropletDyn<SIZE>> {
+        if !self.fits(size) {
+            return None;
+        }
+        let dst: *mut u8 = unsafe { self.buf.add(self.offset) };
+        let lake: *mut dyn LakeMeta = force_static!(self as *mut Self as *mut (dyn LakeMeta + 'a));
+        let droplet = DropletDyn {
+            ptr: unsafe { NonNull::new_unchecked(dst) },
+            len: size,
+            offset: self.offset + size,
+            cursor: 0,
+            lake,
+            generation: self.generation,
+        };
+        self.offset += size;
+        Some(droplet)
+    }
+    /// Like `Lake::alloc_aligned`. The view may start anywhere in its parent,
+    /// so the padding is computed from the real address.
+    #[inline(always)]
+    pub fn alloc_aligned<const N: usize, const ALIGN: usize>(&mut self) -> Option<Droplet<N, LakeView<'a, SIZE>>> {
+        const { assert!(ALIGN.is_power_of_two(), "ALIGN must be a power of two") };
+        let ptr: *mut [u8; N] = self.try_bump(N, ALIGN).ok()? as *mut [u8; N];
+        Some(Droplet {
+            ptr: NonNull::new(ptr)?,
+            offset: self.offset,
+            cursor: 0,
+            lake: self as *mut Self,
+            generation: self.generation,
+        })
+    }
+    /// Allocate a runtime-sized droplet with the size and alignment of `layout`.
+    #[inline(always)]
+    pub fn alloc_layout(&mut self, layout: Layout) -> Option<DropletDyn<SIZE>> {
+        let dst: *mut u8 = self.try_bump(layout.size(), layout.align()).ok()?;
+        let lake: *mut dyn LakeMeta = force_static!(self as *mut Self as *mut (dyn LakeMeta + 'a));
+        Some(DropletDyn {
+            ptr: unsafe { NonNull::new_unchecked(dst) },
+            len: layout.size(),
+            offset: self.offset,
+            cursor: 0,
+            lake,
+            generation: self.generation,
+        })
+    }
     /// Same idea as `Lake::process` — create a droplet dynamically
     /// by invoking a closure and copying its result into the lake.
     /// Useful for one-shot encoders, parsers, and temporary transformations.
@@ -70,34 +193,60 @@ impl<'a, const SIZE: usize> LakeView<'a, SIZE> {
     where
         F: FnOnce(usize) -> Vec<u8>,
     {
-        let remaining: usize = SIZE - self.offset;
-        if remaining == 0 {
-            return Err(LakeError::Overflow);
-        }
-
+        self.try_process(|remaining| Ok::<_, Infallible>(f(remaining)))
+    }
+    /// Like `process`, but the closure may fail. Its error comes back as `LakeError::Process`
+    /// and nothing is written into the lake.
+    #[inline(always)]
+    pub fn try_process<F, E>(&mut self, f: F) -> Result<DropletDyn<SIZE>, LakeError>
+    where
+        F: FnOnce(usize) -> Result<Vec<u8>, E>,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
         let offset: usize = self.offset;
         let generation: usize = self.generation;
         let dst: *mut u8 = unsafe { self.buf.add(offset) };
+        let len: usize = process_into(dst, self.remaining(), self.capacity(), f)?;
 
-        let data: Vec<u8> = f(remaining);
-        let len: usize = data.len();
+        self.offset += len;
 
-        if len > remaining {
-            return Err(LakeError::Overflow);
-        }
+        let lake: *mut dyn LakeMeta = force_static!(self as *mut Self as *mut (dyn LakeMeta + 'a));
 
-        unsafe {
-            std::ptr::copy_nonoverlapping(data.as_ptr(), dst, len);
-        }
+        Ok(DropletDyn {
+            ptr: unsafe { NonNull::new_unchecked(dst) },
+            len,
+            offset: self.offset,
+            cursor: 0,
+            lake,
+            generation,
+        })
+    }
+    /// Zero-copy `process`: the closure writes straight into the lake's free water and returns
+    /// how many bytes it produced. Only those bytes are committed; on error nothing is.
+    #[inline(always)]
+    pub fn process_in_place<F, E>(&mut self, f: F) -> Result<DropletDyn<SIZE>, LakeError>
+    where
+        F: FnOnce(&mut [u8]) -> Result<usize, E>,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let remaining: usize = self.remaining();
+        let offset: usize = self.offset;
+        let generation: usize = self.generation;
+        let dst: *mut u8 = unsafe { self.buf.add(offset) };
+
+        // The closure may scribble on all of it, committed or not: secure mode must wipe it all.
+        self.high_water = self.high_water.max(offset + remaining);
+        let len: usize = process_in_place_into(dst, remaining, self.capacity(), f)?;
 
         self.offset += len;
 
-        let lake: *mut dyn LakeMeta = self as *mut Self as *mut dyn LakeMeta;
+        let lake: *mut dyn LakeMeta = force_static!(self as *mut Self as *mut (dyn LakeMeta + 'a));
 
         Ok(DropletDyn {
             ptr: unsafe { NonNull::new_unchecked(dst) },
             len,
-            offset,
+            offset: self.offset,
+            cursor: 0,
             lake,
             generation,
         })
@@ -106,7 +255,7 @@ impl<'a, const SIZE: usize> LakeView<'a, SIZE> {
     /// If `Lake` is a lake, then `LakeView` is a river — and this method is a tributary.
     #[inline(always)]
     pub fn split(&mut self, len: usize) -> Option<LakeView<'_, SIZE>> {
-        if self.offset + len > self.capacity {
+        if !self.fits(len) {
             return None;
         }
 
@@ -115,10 +264,13 @@ impl<'a, const SIZE: usize> LakeView<'a, SIZE> {
             buf: new_ptr,
             capacity: len,
             offset: 0,
-            mark_stack: Vec::new(),
+            mark_stack: MarkStack::default(),
             _marker: PhantomData,
             generation: 0,
+            id: LakeId::default(),
             zeroing: self.zeroing,
+            secure: self.secure,
+            high_water: 0,
         };
         self.offset += len;
         Some(view)
@@ -138,37 +290,82 @@ impl<'a, const SIZE: usize> LakeView<'a, SIZE> {
     pub fn capacity(&self) -> usize {
         self.capacity
     }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.offset == 0
+    }
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.offset == self.capacity
+    }
+    /// Look at the next `N` bytes of this view without allocating them.
+    /// `None` if they would spill past the view's end.
+    #[inline(always)]
+    pub fn peek<const N: usize>(&self) -> Option<&[u8; N]> {
+        if !self.fits(N) {
+            return None;
+        }
+        let ptr: *const [u8; N] = unsafe { self.buf.add(self.offset) as *const [u8; N] };
+        Some(unsafe { &*ptr })
+    }
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.buf, self.offset) }
+    }
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.buf, self.offset) }
+    }
+    /// Rewind by N bytes. Careful: not validated.
+    #[inline(always)]
+    pub fn reset_to(&mut self, n: usize) {
+        self.lower_to(self.offset.saturating_sub(n));
+    }
+    #[inline(always)]
+    pub fn get_offset(&self) -> usize {
+        self.used()
+    }
     /// Clear the view — wipe offset and mark stack, increment generation.
     /// Think of this as letting the river run dry and rerouting it anew.
     #[inline(always)]
     pub fn reset(&mut self) {
-        if self.zeroing {
+        let top: usize = self.offset;
+        self.lower_to(0);
+        if self.zeroing && !self.secure {
             unsafe {
-                std::ptr::write_bytes(self.buf, 0, self.offset);
+                std::ptr::write_bytes(self.buf, 0, top);
             }
         }
-        self.offset = 0;
         self.mark_stack.clear();
         self.generation += 1;
     }
     /// Push a mark to rewind to later.
+    /// Keep the token: `reset_to_mark` needs it to find this exact mark again.
     #[inline(always)]
-    pub fn mark(&mut self) {
-        self.mark_stack.push(self.offset);
+    pub fn mark(&mut self) -> MarkToken {
+        self.push_mark(None)
     }
-    /// Rewind to the most recent mark.
+    /// `mark`, with a name that shows up when the token is debug-printed.
     #[inline(always)]
-    pub fn reset_to_mark(&mut self) {
-        if let Some(mark) = self.mark_stack.pop() {
-            self.offset = mark;
-        }
+    pub fn mark_named(&mut self, name: &'static str) -> MarkToken {
+        self.push_mark(Some(name))
+    }
+    /// Roll back to the mark behind `token`, popping it and every mark pushed after it.
+    /// Fails, and leaves everything as is, if the mark is gone, from an older generation,
+    /// or above the current water level.
+    #[inline(always)]
+    pub fn reset_to_mark(&mut self, token: MarkToken) -> Result<(), LakeError> {
+        self.pop_mark(token)
     }
     /// Update the latest mark to the current position.
     #[inline(always)]
     pub fn move_mark(&mut self) {
-        if let Some(last) = self.mark_stack.last_mut() {
-            *last = self.offset;
-        }
+        self.move_top_mark();
+    }
+    /// Number of marks on the stack.
+    #[inline(always)]
+    pub fn marks(&self) -> usize {
+        self.mark_stack.len()
     }
     /// Reset and call it a day.
     #[inline(always)]
@@ -183,35 +380,28 @@ impl<'a, const SIZE: usize> LakeView<'a, SIZE> {
     pub fn get_zeroing(&mut self) -> bool {
         self.zeroing
     }
+    /// Secure mode, as on `Lake`: every rollback zeroes everything touched since the last wipe
+    /// with volatile writes. Views split from a secure lake start with it on.
+    #[inline(always)]
+    pub fn set_secure(&mut self, state: bool) {
+        self.secure = state;
+    }
+    #[inline(always)]
+    pub fn is_secure(&self) -> bool {
+        self.secure
+    }
 }
 
 impl<'a, const N: usize> LakeAllocatorExt for LakeView<'a, N> {
-    fn alloc_struct<T>(&mut self) -> &mut T {
-        let align: usize = align_of::<T>();
-        let size: usize = size_of::<T>();
-        let offset: usize = align_up(self.offset, align);
-
-        if offset + size > self.capacity {
-            panic!("LakeView overflow");
-        }
-
-        let ptr = unsafe { self.buf.add(offset) as *mut T };
-        self.offset = offset + size;
-        unsafe { &mut *ptr }
+    fn try_alloc_struct<T>(&mut self) -> Result<&mut T, LakeError> {
+        let ptr: *mut T = self.try_bump(size_of::<T>(), align_of::<T>())? as *mut T;
+        Ok(unsafe { &mut *ptr })
     }
 
-    fn alloc_slice<T>(&mut self, count: usize) -> &mut [T] {
-        let align = align_of::<T>();
-        let size = size_of::<T>() * count;
-        let offset = align_up(self.offset, align);
-
-        if offset + size > self.capacity {
-            panic!("LakeView overflow");
-        }
-
-        let ptr = unsafe { self.buf.add(offset) as *mut T };
-        self.offset = offset + size;
-        unsafe { core::slice::from_raw_parts_mut(ptr, count) }
+    fn try_alloc_slice<T>(&mut self, count: usize) -> Result<&mut [T], LakeError> {
+        let size: usize = slice_bytes::<T>(count)?;
+        let ptr: *mut T = self.try_bump(size, align_of::<T>())? as *mut T;
+        Ok(unsafe { core::slice::from_raw_parts_mut(ptr, count) })
     }
 }
 
@@ -225,7 +415,57 @@ impl<const N: usize> LakeMeta for LakeView<'_, N> {
     fn generation(&self) -> usize {
         self.generation
     }
+    fn origin(&self) -> usize {
+        self.id.get()
+    }
     fn capacity(&self) -> usize {
         self.capacity()
     }
+    fn mark_stack(&mut self) -> Option<&mut MarkStack> {
+        Some(&mut self.mark_stack)
+    }
+    fn generation_mut(&mut self) -> Option<&mut usize> {
+        Some(&mut self.generation)
+    }
+    fn set_offset(&mut self, val: usize) {
+        self.lower_to(val);
+    }
+}
+
+// Inherent methods win name lookup, so these forward instead of recursing.
+impl<'a, const SIZE: usize> LakeAlloc for LakeView<'a, SIZE> {
+    type DynDroplet = DropletDyn<SIZE>;
+    #[inline(always)]
+    fn alloc<const N: usize>(&mut self) -> Option<Droplet<N, Self>> {
+        self.alloc::<N>()
+    }
+    #[inline(always)]
+    fn alloc_dyn(&mut self, size: usize) -> Option<DropletDyn<SIZE>> {
+        self.alloc_dyn(size)
+    }
+    #[inline(always)]
+    fn peek<const N: usize>(&self) -> Option<&[u8; N]> {
+        self.peek::<N>()
+    }
+    #[inline(always)]
+    fn as_slice(&self) -> &[u8] {
+        self.as_slice()
+    }
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.reset()
+    }
+}
+
+impl<const N: usize> Marks for LakeView<'_, N> {
+    #[inline(always)]
+    fn marks_mut(&mut self) -> &mut MarkStack {
+        &mut self.mark_stack
+    }
+}
+
+impl<const N: usize> LakeBuffer for LakeView<'_, N> {
+    fn buf_ptr(&mut self) -> *mut u8 {
+        self.buf
+    }
 }
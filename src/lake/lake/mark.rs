@@ -0,0 +1,138 @@
+use crate::lake::{LakeError, LakeMeta};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Source of mark ids, shared by every lake so a token only ever matches the stack it came from.
+static NEXT_MARK_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Receipt for a mark on a lake's mark stack, handed out by `mark`.
+///
+/// `reset_to_mark(token)` pops back to exactly this mark, no matter what was pushed on top of it
+/// since — nested scopes can't rewind each other by accident. A token from before a `reset`,
+/// from another lake, or whose mark was already popped, is refused instead of rewinding someone
+/// else's water.
+#[must_use]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MarkToken {
+    /// Position on the mark stack; 0 is the outermost mark.
+    pub depth: usize,
+    /// Lake generation the mark was taken in.
+    pub generation: usize,
+    /// Label from `mark_named`, handy in `Debug` output.
+    pub name: Option<&'static str>,
+    /// Tells this mark apart from a newer one at the same depth, or one on another lake.
+    id: usize,
+}
+
+/// `mark`/`reset_to_mark` for every lake type. Rewinds go through `LakeMeta::set_offset`,
+/// so tracked values, secure wiping and red zones see them like any other rollback.
+pub(crate) trait Marks: LakeMeta {
+    fn marks_mut(&mut self) -> &mut MarkStack;
+    /// Remember the current water level and hand out its token.
+    #[inline(always)]
+    fn push_mark(&mut self, name: Option<&'static str>) -> MarkToken {
+        let (offset, generation): (usize, usize) = (self.offset(), self.generation());
+        self.marks_mut().push(offset, generation, name)
+    }
+    /// Pop back to `token`'s mark and lower the water level to it.
+    #[inline(always)]
+    fn pop_mark(&mut self, token: MarkToken) -> Result<(), LakeError> {
+        let (offset, generation): (usize, usize) = (self.offset(), self.generation());
+        let mark: usize = self.marks_mut().pop_to(token, generation, offset)?;
+        self.set_offset(mark);
+        Ok(())
+    }
+    /// Move the most recent mark to the current water level.
+    #[inline(always)]
+    fn move_top_mark(&mut self) {
+        let offset: usize = self.offset();
+        self.marks_mut().move_top(offset);
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Mark {
+    offset: usize,
+    id: usize,
+}
+
+/// The marks behind `mark`/`reset_to_mark`, oldest first.
+#[derive(Clone, Default)]
+pub struct MarkStack {
+    marks: Vec<Mark>,
+}
+
+impl MarkStack {
+    #[inline(always)]
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            marks: Vec::with_capacity(capacity),
+        }
+    }
+    /// Remember `offset` and hand out its token.
+    #[inline(always)]
+    pub(crate) fn push(&mut self, offset: usize, generation: usize, name: Option<&'static str>) -> MarkToken {
+        // Never reused, not across resets and not across lakes.
+        let id: usize = NEXT_MARK_ID.fetch_add(1, Ordering::Relaxed);
+        self.marks.push(Mark { offset, id });
+        MarkToken {
+            depth: self.marks.len() - 1,
+            generation,
+            name,
+            id,
+        }
+    }
+    /// Pop everything down to and including `token`'s mark and return its offset.
+    /// Nothing is popped if the token is stale or the lake already sits below the mark.
+    #[inline(always)]
+    pub(crate) fn pop_to(&mut self, token: MarkToken, generation: usize, current: usize) -> Result<usize, LakeError> {
+        if token.generation != generation {
+            return Err(LakeError::StaleGeneration {
+                expected: token.generation,
+                found: generation,
+            });
+        }
+        let mark: Mark = match self.marks.get(token.depth) {
+            Some(mark) if mark.id == token.id => *mark,
+            _ => {
+                return Err(LakeError::InvalidMark {
+                    depth: token.depth,
+                    marks: self.marks.len(),
+                })
+            }
+        };
+        if mark.offset > current {
+            return Err(LakeError::InvalidSnapshot {
+                offset: mark.offset,
+                current,
+            });
+        }
+        self.marks.truncate(token.depth);
+        Ok(mark.offset)
+    }
+    /// Move the most recent mark to `offset`. Its token stays valid.
+    #[inline(always)]
+    pub(crate) fn move_top(&mut self, offset: usize) {
+        if let Some(last) = self.marks.last_mut() {
+            last.offset = offset;
+        }
+    }
+    /// Put back a copy taken earlier. Ids handed out since stay burned, so tokens from
+    /// the discarded marks can't match anything pushed later.
+    #[inline(always)]
+    pub(crate) fn restore(&mut self, saved: MarkStack) {
+        *self = saved;
+    }
+    #[inline(always)]
+    pub(crate) fn clear(&mut self) {
+        self.marks.clear();
+    }
+    /// Number of marks on the stack.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.marks.len()
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.marks.is_empty()
+    }
+}
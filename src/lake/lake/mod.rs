@@ -1,4 +1,18 @@
+#[cfg(feature = "allocator-api2")]
+pub mod allocator;
+pub mod chained_lake;
+pub mod drop_list;
+pub mod dyn_lake;
+pub mod journal;
 pub mod lake;
 pub mod lake_view;
+pub mod mark;
+pub mod pool;
+pub(crate) mod raw_lake;
 pub mod sandbox;
+#[cfg(feature = "sanitizer")]
+pub mod sanitizer;
+pub mod shared_lake;
+pub mod snapshot_guard;
 pub mod thread_lake;
+pub mod transaction;
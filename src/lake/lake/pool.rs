@@ -0,0 +1,160 @@
+use crate::lake::lake::lake::Lake;
+use crate::lake::{LakeMeta, LakeStats};
+use std::sync::{Mutex, MutexGuard};
+
+/// What a `LakePool` has been up to.
+#[derive(Debug, Clone, Copy)]
+pub struct LakePoolStats {
+    /// `LakeMeta::stats()` summed over the idle lakes, with every lake on loan counted as
+    /// full (the pool can't see how much of it is filled). `generation` is the newest idle one.
+    pub lakes: LakeStats,
+    /// Lakes waiting in the pool.
+    pub idle: usize,
+    /// Lakes handed out and not returned yet.
+    pub in_use: usize,
+    /// Lakes created because the pool was empty.
+    pub created: usize,
+    /// Lakes reused from the pool.
+    pub reused: usize,
+    /// Returned lakes kept for another trip.
+    pub recycled: usize,
+    /// Returned lakes dropped because the pool was already full.
+    pub discarded: usize,
+    /// How many idle lakes the pool keeps at most.
+    pub max_idle: usize,
+}
+
+struct PoolInner<const SIZE: usize> {
+    idle: Vec<Lake<SIZE>>,
+    in_use: usize,
+    created: usize,
+    reused: usize,
+    recycled: usize,
+    discarded: usize,
+}
+
+/// A pool of owned lakes that can travel between threads.
+///
+/// An I/O thread `acquire`s a lake, fills it, and hands the whole lake to a worker;
+/// the worker `release`s it when done. The lake comes back `reset` — fresh water,
+/// next generation — and waits for its next trip. At most `max_idle` lakes are kept;
+/// the rest evaporate.
+///
+/// The pool is `Sync`: share it through an `Arc` or a `static`.
+pub struct LakePool<const SIZE: usize> {
+    inner: Mutex<PoolInner<SIZE>>,
+    max_idle: usize,
+}
+
+impl<const SIZE: usize> LakePool<SIZE> {
+    /// Create an empty pool that retains at most `max_idle` returned lakes.
+    #[inline(always)]
+    pub fn new(max_idle: usize) -> Self {
+        Self {
+            inner: Mutex::new(PoolInner {
+                idle: Vec::with_capacity(max_idle),
+                in_use: 0,
+                created: 0,
+                reused: 0,
+                recycled: 0,
+                discarded: 0,
+            }),
+            max_idle,
+        }
+    }
+    /// Create a pool already holding `count` idle lakes (capped at `max_idle`).
+    #[inline(always)]
+    pub fn with_idle(max_idle: usize, count: usize) -> Self {
+        let pool: Self = Self::new(max_idle);
+        {
+            let mut inner = pool.lock();
+            let count: usize = count.min(max_idle);
+            inner.idle.extend((0..count).map(|_| Lake::new()));
+            inner.created += count;
+        }
+        pool
+    }
+    /// A poisoned pool is still a pool: lakes are reset on the way in, so there is no
+    /// half-written state to worry about.
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<'_, PoolInner<SIZE>> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+    /// Take a lake out of the pool, or create one if the pool is dry.
+    #[inline(always)]
+    pub fn acquire(&self) -> Lake<SIZE> {
+        let mut inner = self.lock();
+        inner.in_use += 1;
+        match inner.idle.pop() {
+            Some(lake) => {
+                inner.reused += 1;
+                lake
+            }
+            None => {
+                inner.created += 1;
+                drop(inner);
+                Lake::new()
+            }
+        }
+    }
+    /// Give a lake back. It is reset right away; if the pool is full, it is dropped instead.
+    /// Secure mode, zeroing and the sanitizer are switched off after the reset, so the next
+    /// borrower starts from `Lake::new()` settings. A `lock_memory` stays in place.
+    #[inline(always)]
+    pub fn release(&self, mut lake: Lake<SIZE>) {
+        lake.reset();
+        lake.set_secure(false);
+        lake.set_zeroing(false);
+        #[cfg(feature = "sanitizer")]
+        lake.set_sanitizer(false);
+        let mut inner = self.lock();
+        inner.in_use = inner.in_use.saturating_sub(1);
+        if inner.idle.len() < self.max_idle {
+            inner.recycled += 1;
+            inner.idle.push(lake);
+        } else {
+            inner.discarded += 1;
+        }
+    }
+    /// Drop every idle lake, giving the memory back.
+    #[inline(always)]
+    pub fn clear(&self) {
+        let idle: Vec<Lake<SIZE>> = std::mem::take(&mut self.lock().idle);
+        drop(idle);
+    }
+    #[inline(always)]
+    pub fn max_idle(&self) -> usize {
+        self.max_idle
+    }
+    /// Snapshot of the pool's counters.
+    #[inline(always)]
+    pub fn stats(&self) -> LakePoolStats {
+        let inner = self.lock();
+        let idle: usize = inner.idle.len();
+        let on_loan: usize = inner.in_use * SIZE;
+        let lakes: LakeStats = inner.idle.iter().map(LakeMeta::stats).fold(
+            LakeStats {
+                used: on_loan,
+                remaining: 0,
+                capacity: on_loan,
+                generation: 0,
+            },
+            |total, lake| LakeStats {
+                used: total.used + lake.used,
+                remaining: total.remaining + lake.remaining,
+                capacity: total.capacity + lake.capacity,
+                generation: total.generation.max(lake.generation),
+            },
+        );
+        LakePoolStats {
+            lakes,
+            idle,
+            in_use: inner.in_use,
+            created: inner.created,
+            reused: inner.reused,
+            recycled: inner.recycled,
+            discarded: inner.discarded,
+            max_idle: self.max_idle,
+        }
+    }
+}
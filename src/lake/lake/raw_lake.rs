@@ -0,0 +1,745 @@
+use crate::lake::droplet::droplet::Droplet;
+use crate::lake::droplet::droplet_dyn::DropletDyn;
+use crate::lake::lake::drop_list::DropList;
+use crate::lake::lake::lake_view::LakeView;
+use crate::lake::lake::mark::MarkStack;
+#[cfg(feature = "sanitizer")]
+use crate::lake::lake::sanitizer::Sanitizer;
+use crate::lake::memory::{secure_zero, MemoryLock};
+use crate::lake::utils::align_up;
+use crate::lake::{slice_bytes, LakeError, LakeId, LakeMeta};
+use std::{alloc::Layout, error::Error, marker::PhantomData, ptr::NonNull};
+
+/// The water and everything that keeps it in order, behind both `Lake` and `DynLake`.
+///
+/// `B` is where the water lives: a `Water<SIZE>` for `Lake`, a `DynWater` for `DynLake`. Droplets point
+/// at the wrapper, not at this, so every method that carves one takes the wrapper's pointer.
+pub(crate) struct RawLake<B: ?Sized + AsRef<[u8]> + AsMut<[u8]>> {
+    /// Destructors owed by tracked values. Declared first, so they run while the buffer is still around.
+    pub(super) drops: DropList,
+    /// `mlock` on the buffer, if any. Declared before it, so it is released while the buffer still exists.
+    pub(super) lock: MemoryLock,
+    /// Our "water reservoir" – preallocated and boxed for stable address.
+    pub(super) buf: Box<B>,
+    /// Current fill level of the lake (offset from the beginning).
+    pub(super) offset: usize,
+    /// Stack of marks for scoped rewinds.
+    pub(super) mark_stack: MarkStack,
+    /// Generation counter to guard against stale droplets.
+    pub(super) generation: usize,
+    /// Who this is, for `origin`. Fresh for every lake and every clone.
+    pub(super) id: LakeId,
+    /// Wipe data with 0u8 while reset
+    pub(super) zeroing: bool,
+    /// Volatile-wipe everything given back on every rollback and on drop.
+    pub(super) secure: bool,
+    /// Highest water level seen since the last secure wipe. Nothing above it needs wiping.
+    pub(super) high_water: usize,
+    /// Red zones and poison, for hunting down droplets that write past their end.
+    #[cfg(feature = "sanitizer")]
+    pub(super) sanitizer: Sanitizer,
+}
+
+impl<B: ?Sized + AsRef<[u8]> + AsMut<[u8]>> RawLake<B> {
+    #[inline(always)]
+    pub(super) fn new(buf: Box<B>) -> Self {
+        Self {
+            buf,
+            offset: 0,
+            mark_stack: MarkStack::with_capacity(100),
+            drops: DropList::default(),
+            generation: 0,
+            id: LakeId::default(),
+            zeroing: false,
+            lock: MemoryLock::default(),
+            secure: false,
+            high_water: 0,
+            #[cfg(feature = "sanitizer")]
+            sanitizer: Sanitizer::default(),
+        }
+    }
+    #[inline(always)]
+    fn water(&self) -> &[u8] {
+        (*self.buf).as_ref()
+    }
+    #[inline(always)]
+    fn water_mut(&mut self) -> &mut [u8] {
+        (*self.buf).as_mut()
+    }
+    #[inline(always)]
+    pub(super) fn capacity(&self) -> usize {
+        self.water().len()
+    }
+    #[inline(always)]
+    pub(super) fn remaining(&self) -> usize {
+        self.capacity() - self.offset
+    }
+    /// Carve `size` bytes aligned to `align`, or explain why the lake can't.
+    #[inline(always)]
+    fn try_bump(&mut self, size: usize, align: usize) -> Result<*mut u8, LakeError> {
+        // Wider alignments than the buffer's own need the real address, not the offset.
+        let base: usize = self.water().as_ptr() as usize;
+        let offset: usize = align_up(base + self.offset, align) - base;
+        match offset.checked_add(size) {
+            Some(end) if end <= self.capacity() => {
+                self.offset = end;
+                Ok(unsafe { self.water_mut().as_mut_ptr().add(offset) })
+            }
+            _ => Err(self.overflow(size, align)),
+        }
+    }
+    #[inline(always)]
+    fn overflow(&self, requested: usize, align: usize) -> LakeError {
+        LakeError::Overflow {
+            requested,
+            align,
+            remaining: self.remaining(),
+            capacity: self.capacity(),
+        }
+    }
+    /// Fence the allocation from `start` up to the water level with a red zone.
+    /// A no-op unless the `sanitizer` feature is on and enabled for this lake.
+    #[inline(always)]
+    fn seal(&mut self, start: usize) {
+        #[cfg(feature = "sanitizer")]
+        {
+            self.offset = self.sanitizer.after_alloc((*self.buf).as_mut(), start, self.offset);
+        }
+        #[cfg(not(feature = "sanitizer"))]
+        let _ = start;
+    }
+    /// Drop tracked values at or above `offset` and move the water level there.
+    /// In secure mode everything between `offset` and the high-water mark is wiped first.
+    #[inline(always)]
+    pub(super) fn lower_to(&mut self, offset: usize) {
+        self.drops.release(offset);
+        #[cfg(feature = "sanitizer")]
+        self.sanitizer.before_rewind((*self.buf).as_mut(), offset, self.offset, !self.secure);
+        self.high_water = self.high_water.max(self.offset);
+        if self.secure {
+            if self.high_water > offset {
+                let high_water: usize = self.high_water;
+                secure_zero(&mut self.water_mut()[offset..high_water]);
+            }
+            self.high_water = offset;
+        }
+        self.offset = offset;
+    }
+    /// Hand the next `len` bytes to a `LakeView`. They stay allocated here until rewound.
+    #[inline(always)]
+    pub(super) fn split<const S: usize>(&mut self, len: usize) -> Result<LakeView<'static, S>, LakeError> {
+        if len > self.remaining() {
+            return Err(self.overflow(len, 1));
+        }
+
+        let view = LakeView {
+            buf: unsafe { self.water_mut().as_mut_ptr().add(self.offset) },
+            capacity: len,
+            offset: 0,
+            mark_stack: MarkStack::default(),
+            _marker: PhantomData,
+            generation: 0,
+            id: LakeId::default(),
+            zeroing: self.zeroing,
+            secure: self.secure,
+            high_water: 0,
+        };
+
+        self.offset += len;
+        self.seal(self.offset - len);
+        Ok(view)
+    }
+    #[inline(always)]
+    pub(super) fn try_process<F, E, const S: usize>(
+        &mut self,
+        lake: *mut dyn LakeMeta,
+        f: F,
+    ) -> Result<DropletDyn<S>, LakeError>
+    where
+        F: FnOnce(usize) -> Result<Vec<u8>, E>,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let offset: usize = self.offset;
+        let generation: usize = self.generation;
+        let dst: *mut u8 = unsafe { self.water_mut().as_mut_ptr().add(offset) };
+        let len: usize = process_into(dst, self.remaining(), self.capacity(), f)?;
+
+        self.offset += len;
+        self.seal(offset);
+
+        Ok(DropletDyn {
+            ptr: unsafe { NonNull::new_unchecked(dst) },
+            len,
+            offset: offset + len,
+            cursor: 0,
+            lake,
+            generation,
+        })
+    }
+    #[inline(always)]
+    pub(super) fn process_in_place<F, E, const S: usize>(
+        &mut self,
+        lake: *mut dyn LakeMeta,
+        f: F,
+    ) -> Result<DropletDyn<S>, LakeError>
+    where
+        F: FnOnce(&mut [u8]) -> Result<usize, E>,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let remaining: usize = self.remaining();
+        let offset: usize = self.offset;
+        let generation: usize = self.generation;
+        let dst: *mut u8 = unsafe { self.water_mut().as_mut_ptr().add(offset) };
+
+        // The closure may scribble on all of it, committed or not: secure mode must wipe it all.
+        self.high_water = self.high_water.max(offset + remaining);
+        let len: usize = process_in_place_into(dst, remaining, self.capacity(), f)?;
+
+        self.offset += len;
+        self.seal(offset);
+
+        Ok(DropletDyn {
+            ptr: unsafe { NonNull::new_unchecked(dst) },
+            len,
+            offset: offset + len,
+            cursor: 0,
+            lake,
+            generation,
+        })
+    }
+    #[inline(always)]
+    pub(super) fn alloc<const N: usize, L: LakeMeta>(&mut self, lake: *mut L) -> Option<Droplet<N, L>> {
+        if N > self.remaining() {
+            return None;
+        }
+        let start: usize = self.offset;
+        let ptr: *mut [u8; N] = unsafe { self.water_mut().as_mut_ptr().add(start) as *mut [u8; N] };
+        let droplet = Droplet {
+            ptr: NonNull::new(ptr)?,
+            offset: start + N,
+            cursor: 0,
+            lake,
+            generation: self.generation,
+        };
+        self.offset += N;
+        self.seal(start);
+        Some(droplet)
+    }
+    #[inline(always)]
+    pub(super) fn alloc_dyn<const S: usize>(&mut self, size: usize, lake: *mut dyn LakeMeta) -> Option<DropletDyn<S>> {
+        if size > self.remaining() {
+            return None;
+        }
+        let start: usize = self.offset;
+        let dst: *mut u8 = unsafe { self.water_mut().as_mut_ptr().add(start) };
+        let droplet = DropletDyn {
+            ptr: unsafe { NonNull::new_unchecked(dst) },
+            len: size,
+            offset: start + size,
+            cursor: 0,
+            lake,
+            generation: self.generation,
+        };
+        self.offset += size;
+        self.seal(start);
+        Some(droplet)
+    }
+    #[inline(always)]
+    pub(super) fn alloc_aligned<const N: usize, const ALIGN: usize, L: LakeMeta>(
+        &mut self,
+        lake: *mut L,
+    ) -> Option<Droplet<N, L>> {
+        const { assert!(ALIGN.is_power_of_two(), "ALIGN must be a power of two") };
+        let ptr: *mut [u8; N] = self.try_bump(N, ALIGN).ok()? as *mut [u8; N];
+        let droplet = Droplet {
+            ptr: NonNull::new(ptr)?,
+            offset: self.offset,
+            cursor: 0,
+            lake,
+            generation: self.generation,
+        };
+        self.seal(self.offset - N);
+        Some(droplet)
+    }
+    #[inline(always)]
+    pub(super) fn alloc_layout<const S: usize>(
+        &mut self,
+        layout: Layout,
+        lake: *mut dyn LakeMeta,
+    ) -> Option<DropletDyn<S>> {
+        let dst: *mut u8 = self.try_bump(layout.size(), layout.align()).ok()?;
+        let droplet = DropletDyn {
+            ptr: unsafe { NonNull::new_unchecked(dst) },
+            len: layout.size(),
+            offset: self.offset,
+            cursor: 0,
+            lake,
+            generation: self.generation,
+        };
+        self.seal(self.offset - layout.size());
+        Some(droplet)
+    }
+    #[inline(always)]
+    pub(super) fn try_alloc_struct<T>(&mut self) -> Result<&mut T, LakeError> {
+        let ptr: *mut T = self.try_bump(size_of::<T>(), align_of::<T>())? as *mut T;
+        self.seal(self.offset - size_of::<T>());
+        Ok(unsafe { &mut *ptr })
+    }
+    #[inline(always)]
+    pub(super) fn try_alloc_slice<T>(&mut self, count: usize) -> Result<&mut [T], LakeError> {
+        let size: usize = slice_bytes::<T>(count)?;
+        let ptr: *mut T = self.try_bump(size, align_of::<T>())? as *mut T;
+        self.seal(self.offset - size);
+        Ok(unsafe { core::slice::from_raw_parts_mut(ptr, count) })
+    }
+    #[inline(always)]
+    pub(super) fn reset(&mut self) {
+        let top: usize = self.offset;
+        self.lower_to(0);
+        if self.zeroing && !self.secure {
+            self.water_mut()[..top].fill(0);
+        }
+        self.mark_stack.clear();
+        self.generation += 1;
+    }
+    #[inline(always)]
+    pub(super) fn as_slice(&self) -> &[u8] {
+        &self.water()[..self.offset]
+    }
+    #[inline(always)]
+    pub(super) fn as_mut_slice(&mut self) -> &mut [u8] {
+        let offset: usize = self.offset;
+        &mut self.water_mut()[..offset]
+    }
+    #[inline(always)]
+    pub(super) fn peek<const N: usize>(&self) -> Option<&[u8; N]> {
+        self.water().get(self.offset..self.offset.checked_add(N)?)?.try_into().ok()
+    }
+    #[inline(always)]
+    pub(super) fn lock_memory(&mut self) -> Result<(), LakeError> {
+        self.lock.lock((*self.buf).as_ref()).map_err(LakeError::MemoryLock)
+    }
+    #[cfg(feature = "sanitizer")]
+    #[inline(always)]
+    pub(super) fn check_red_zone(&self, end: usize) {
+        self.sanitizer.check_zone(self.water(), end);
+    }
+    #[inline(always)]
+    pub(super) fn buf_ptr(&mut self) -> *mut u8 {
+        self.water_mut().as_mut_ptr()
+    }
+}
+
+impl<B: ?Sized + AsRef<[u8]> + AsMut<[u8]>> Clone for RawLake<B>
+where
+    Box<B>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            drops: self.drops.clone(),
+            lock: self.lock.clone(),
+            buf: self.buf.clone(),
+            offset: self.offset,
+            mark_stack: self.mark_stack.clone(),
+            generation: self.generation,
+            id: self.id.clone(),
+            zeroing: self.zeroing,
+            secure: self.secure,
+            high_water: self.high_water,
+            #[cfg(feature = "sanitizer")]
+            sanitizer: self.sanitizer.clone(),
+        }
+    }
+}
+
+impl<B: ?Sized + AsRef<[u8]> + AsMut<[u8]>> Drop for RawLake<B> {
+    fn drop(&mut self) {
+        if self.secure {
+            self.lower_to(0);
+        }
+    }
+}
+
+/// The closure half of `try_process`, shared by every lake type: hand `f` the `remaining`
+/// free bytes' worth of room, then copy what it produced to `dst`. Returns the length;
+/// moving the water level is up to the caller.
+#[inline(always)]
+pub(super) fn process_into<F, E>(dst: *mut u8, remaining: usize, capacity: usize, f: F) -> Result<usize, LakeError>
+where
+    F: FnOnce(usize) -> Result<Vec<u8>, E>,
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    let overflow = |requested: usize| LakeError::Overflow {
+        requested,
+        align: 1,
+        remaining,
+        capacity,
+    };
+    if remaining == 0 {
+        return Err(overflow(0));
+    }
+
+    let data: Vec<u8> = f(remaining).map_err(|err| LakeError::Process(err.into()))?;
+    let len: usize = data.len();
+
+    if len > remaining {
+        return Err(overflow(len));
+    }
+
+    // We trust the closure not to lie. Now copy the result into the lake.
+    unsafe {
+        std::ptr::copy_nonoverlapping(data.as_ptr(), dst, len);
+    }
+    Ok(len)
+}
+
+/// The closure half of `process_in_place`: lend `f` the `remaining` free bytes at `dst`
+/// and check the length it claims to have written.
+#[inline(always)]
+pub(super) fn process_in_place_into<F, E>(dst: *mut u8, remaining: usize, capacity: usize, f: F) -> Result<usize, LakeError>
+where
+    F: FnOnce(&mut [u8]) -> Result<usize, E>,
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    let overflow = |requested: usize| LakeError::Overflow {
+        requested,
+        align: 1,
+        remaining,
+        capacity,
+    };
+    if remaining == 0 {
+        return Err(overflow(0));
+    }
+
+    let free: &mut [u8] = unsafe { std::slice::from_raw_parts_mut(dst, remaining) };
+    let len: usize = f(free).map_err(|err| LakeError::Process(err.into()))?;
+
+    if len > remaining {
+        return Err(overflow(len));
+    }
+    Ok(len)
+}
+
+/// Everything `Lake` and `DynLake` share, forwarded to their `raw: RawLake<_>` field:
+/// `raw_lake_api!([generics] Type, DynDropletType)`.
+/// Each wrapper still brings its own `new` and `capacity`.
+macro_rules! raw_lake_api {
+    ([$($generics:tt)*] $ty:ty, $dyn_droplet:ty) => {
+        impl<$($generics)*> $ty {
+            #[inline(always)]
+            pub fn freeze_ref(&mut self) -> &Self {
+                &*self
+            }
+            #[inline(always)]
+            pub fn freeze_ptr(&self) -> *const Self {
+                self as *const Self
+            }
+            /// Take a snapshot of the lake's current water level.
+            #[inline(always)]
+            pub fn snapshot(&self) -> $crate::lake::LakeSnapshot {
+                $crate::lake::LakeMeta::snapshot(self)
+            }
+            /// Rewind to a previous snapshot (rollback to known-safe state).
+            #[inline(always)]
+            pub fn rewind(&mut self, snapshot: $crate::lake::LakeSnapshot) {
+                $crate::lake::LakeMeta::rewind(self, snapshot)
+            }
+            /// Like `rewind`, but a snapshot from another lake, an older generation or above
+            /// the current water level comes back as an error instead of a panic.
+            #[inline(always)]
+            pub fn try_rewind(&mut self, snapshot: $crate::lake::LakeSnapshot) -> Result<(), $crate::lake::LakeError> {
+                $crate::lake::LakeMeta::try_rewind(self, snapshot)
+            }
+            /// Invoke a closure to produce data into the lake, returning a dynamic droplet.
+            /// Perfect for encoding, compression, or other on-the-fly data shaping.
+            #[inline(always)]
+            pub fn process<F>(&mut self, f: F) -> Result<$dyn_droplet, $crate::lake::LakeError>
+            where
+                F: FnOnce(usize) -> Vec<u8>,
+            {
+                self.try_process(|remaining| Ok::<_, ::std::convert::Infallible>(f(remaining)))
+            }
+            /// Like `process`, but the closure may fail. Its error comes back as `LakeError::Process`
+            /// and nothing is written into the lake.
+            #[inline(always)]
+            pub fn try_process<F, E>(&mut self, f: F) -> Result<$dyn_droplet, $crate::lake::LakeError>
+            where
+                F: FnOnce(usize) -> Result<Vec<u8>, E>,
+                E: Into<Box<dyn ::std::error::Error + Send + Sync>>,
+            {
+                let lake: *mut dyn $crate::lake::LakeMeta = self as *mut Self as *mut dyn $crate::lake::LakeMeta;
+                self.raw.try_process(lake, f)
+            }
+            /// Zero-copy `process`: the closure writes straight into the lake's free water and returns
+            /// how many bytes it produced. Only those bytes are committed; on error nothing is.
+            #[inline(always)]
+            pub fn process_in_place<F, E>(&mut self, f: F) -> Result<$dyn_droplet, $crate::lake::LakeError>
+            where
+                F: FnOnce(&mut [u8]) -> Result<usize, E>,
+                E: Into<Box<dyn ::std::error::Error + Send + Sync>>,
+            {
+                let lake: *mut dyn $crate::lake::LakeMeta = self as *mut Self as *mut dyn $crate::lake::LakeMeta;
+                self.raw.process_in_place(lake, f)
+            }
+            /// Allocate a fixed-size droplet from the lake.
+            /// No copying. No allocations. Just pure, raw, delicious speed.
+            /// Byte-aligned: reach for `alloc_aligned` when the address matters.
+            #[inline(always)]
+            pub fn alloc<const N: usize>(&mut self) -> Option<$crate::lake::droplet::droplet::Droplet<N, Self>> {
+                let lake: *mut Self = self as *mut Self;
+                self.raw.alloc::<N, Self>(lake)
+            }
+            #[inline(always)]
+            pub fn alloc_dyn(&mut self, size: usize) -> Option<$dyn_droplet> {
+                let lake: *mut dyn $crate::lake::LakeMeta = self as *mut Self as *mut dyn $crate::lake::LakeMeta;
+                self.raw.alloc_dyn(size, lake)
+            }
+            /// Allocate a fixed-size droplet starting on an `ALIGN`-byte boundary (32 for AVX2, 4096 for a page).
+            /// Padding in front of it is skipped, and comes back on rewind like everything else.
+            #[inline(always)]
+            pub fn alloc_aligned<const N: usize, const ALIGN: usize>(
+                &mut self,
+            ) -> Option<$crate::lake::droplet::droplet::Droplet<N, Self>> {
+                let lake: *mut Self = self as *mut Self;
+                self.raw.alloc_aligned::<N, ALIGN, Self>(lake)
+            }
+            /// Allocate a runtime-sized droplet with the size and alignment of `layout`.
+            #[inline(always)]
+            pub fn alloc_layout(&mut self, layout: ::std::alloc::Layout) -> Option<$dyn_droplet> {
+                let lake: *mut dyn $crate::lake::LakeMeta = self as *mut Self as *mut dyn $crate::lake::LakeMeta;
+                self.raw.alloc_layout(layout, lake)
+            }
+            /// Like `alloc`, but the droplet rewinds the lake when dropped, if it is still on top.
+            #[inline(always)]
+            pub fn alloc_scoped<const N: usize>(
+                &mut self,
+            ) -> Option<$crate::lake::droplet::scoped::ScopedDroplet<'_, $crate::lake::droplet::droplet::Droplet<N, Self>>> {
+                let start: usize = self.raw.offset;
+                let droplet = self.alloc::<N>()?;
+                let lake: *mut dyn $crate::lake::LakeMeta = self as *mut Self as *mut dyn $crate::lake::LakeMeta;
+                Some($crate::lake::droplet::scoped::ScopedDroplet::new(droplet, lake, start))
+            }
+            /// Like `alloc_dyn`, but the droplet rewinds the lake when dropped, if it is still on top.
+            #[inline(always)]
+            pub fn alloc_dyn_scoped(
+                &mut self,
+                size: usize,
+            ) -> Option<$crate::lake::droplet::scoped::ScopedDroplet<'_, $dyn_droplet>> {
+                let start: usize = self.raw.offset;
+                let droplet = self.alloc_dyn(size)?;
+                let lake: *mut dyn $crate::lake::LakeMeta = self as *mut Self as *mut dyn $crate::lake::LakeMeta;
+                Some($crate::lake::droplet::scoped::ScopedDroplet::new(droplet, lake, start))
+            }
+            /// Wipe the lake clean and start a new generation. Fresh waters.
+            #[inline(always)]
+            pub fn reset(&mut self) {
+                self.raw.reset();
+            }
+            /// Returns used capacity.
+            #[inline(always)]
+            pub fn used(&self) -> usize {
+                self.raw.offset
+            }
+            /// Returns remaining capacity.
+            #[inline(always)]
+            pub fn remaining(&self) -> usize {
+                self.raw.remaining()
+            }
+            #[inline(always)]
+            pub fn is_empty(&self) -> bool {
+                self.raw.offset == 0
+            }
+            #[inline(always)]
+            pub fn is_full(&self) -> bool {
+                self.raw.remaining() == 0
+            }
+            #[inline(always)]
+            pub fn as_slice(&self) -> &[u8] {
+                self.raw.as_slice()
+            }
+            #[inline(always)]
+            pub fn as_mut_slice(&mut self) -> &mut [u8] {
+                self.raw.as_mut_slice()
+            }
+            /// Preview what the next allocation would look like.
+            #[inline(always)]
+            pub fn peek<const N: usize>(&self) -> Option<&[u8; N]> {
+                self.raw.peek::<N>()
+            }
+            /// Rewind by N bytes. Careful: not validated.
+            #[inline(always)]
+            pub fn reset_to(&mut self, n: usize) {
+                self.raw.lower_to(self.raw.offset.saturating_sub(n));
+            }
+            #[inline(always)]
+            pub fn get_offset(&self) -> usize {
+                self.used()
+            }
+            /// Mark the current position (you can come back to it later).
+            /// Keep the token: `reset_to_mark` needs it to find this exact mark again.
+            #[inline(always)]
+            pub fn mark(&mut self) -> $crate::lake::lake::mark::MarkToken {
+                $crate::lake::lake::mark::Marks::push_mark(self, None)
+            }
+            /// `mark`, with a name that shows up when the token is debug-printed.
+            #[inline(always)]
+            pub fn mark_named(&mut self, name: &'static str) -> $crate::lake::lake::mark::MarkToken {
+                $crate::lake::lake::mark::Marks::push_mark(self, Some(name))
+            }
+            /// Roll back to the mark behind `token`, popping it and every mark pushed after it.
+            /// Fails, and leaves everything as is, if the mark is gone, from an older generation,
+            /// or above the current water level.
+            #[inline(always)]
+            pub fn reset_to_mark(
+                &mut self,
+                token: $crate::lake::lake::mark::MarkToken,
+            ) -> Result<(), $crate::lake::LakeError> {
+                $crate::lake::lake::mark::Marks::pop_mark(self, token)
+            }
+            /// Move the most recent mark to the current offset.
+            #[inline(always)]
+            pub fn move_mark(&mut self) {
+                $crate::lake::lake::mark::Marks::move_top_mark(self);
+            }
+            /// Number of marks on the stack.
+            #[inline(always)]
+            pub fn marks(&self) -> usize {
+                self.raw.mark_stack.len()
+            }
+            /// Wipe everything. Same as `reset`, but sounds more decisive.
+            #[inline(always)]
+            pub fn clear(&mut self) {
+                self.reset();
+            }
+            #[inline(always)]
+            pub fn set_zeroing(&mut self, state: bool) {
+                self.raw.zeroing = state;
+            }
+            #[inline(always)]
+            pub fn get_zeroing(&mut self) -> bool {
+                self.raw.zeroing
+            }
+            /// Secure mode, for keys and tokens: every rollback (`reset`, `rewind`, `reset_to`,
+            /// `reset_to_mark`, sandboxes) and dropping the lake zero everything touched since the last wipe,
+            /// with volatile writes the optimizer can't elide. Replaces `zeroing` while on.
+            #[inline(always)]
+            pub fn set_secure(&mut self, state: bool) {
+                self.raw.secure = state;
+            }
+            #[inline(always)]
+            pub fn is_secure(&self) -> bool {
+                self.raw.secure
+            }
+            /// `mlock` the whole buffer so it never hits swap. Unlocked when the lake is dropped;
+            /// clones are not locked.
+            #[inline(always)]
+            pub fn lock_memory(&mut self) -> Result<(), $crate::lake::LakeError> {
+                self.raw.lock_memory()
+            }
+            #[inline(always)]
+            pub fn is_memory_locked(&self) -> bool {
+                self.raw.lock.is_locked()
+            }
+            /// Turn on red zones, poisoning and canary checks for this lake (`sanitizer` feature only).
+            /// Allocations are no longer packed back to back while it's on, so turn it on while the lake is empty.
+            #[cfg(feature = "sanitizer")]
+            #[inline(always)]
+            pub fn set_sanitizer(&mut self, state: bool) {
+                self.raw.sanitizer.set_enabled(state);
+            }
+            #[cfg(feature = "sanitizer")]
+            #[inline(always)]
+            pub fn is_sanitized(&self) -> bool {
+                self.raw.sanitizer.is_enabled()
+            }
+        }
+
+        impl<$($generics)*> $crate::lake::LakeAllocatorExt for $ty {
+            fn try_alloc_struct<T>(&mut self) -> Result<&mut T, $crate::lake::LakeError> {
+                self.raw.try_alloc_struct::<T>()
+            }
+
+            fn try_alloc_slice<T>(&mut self, count: usize) -> Result<&mut [T], $crate::lake::LakeError> {
+                self.raw.try_alloc_slice::<T>(count)
+            }
+        }
+
+        impl<$($generics)*> $crate::lake::LakeMeta for $ty {
+            fn offset(&self) -> usize {
+                self.raw.offset
+            }
+            fn origin(&self) -> usize {
+                self.raw.id.get()
+            }
+            fn capacity(&self) -> usize {
+                self.raw.capacity()
+            }
+            fn offset_mut(&mut self) -> &mut usize {
+                &mut self.raw.offset
+            }
+            fn generation(&self) -> usize {
+                self.raw.generation
+            }
+            fn drops(&mut self) -> Option<&mut $crate::lake::lake::drop_list::DropList> {
+                Some(&mut self.raw.drops)
+            }
+            fn mark_stack(&mut self) -> Option<&mut $crate::lake::lake::mark::MarkStack> {
+                Some(&mut self.raw.mark_stack)
+            }
+            fn generation_mut(&mut self) -> Option<&mut usize> {
+                Some(&mut self.raw.generation)
+            }
+            fn set_offset(&mut self, val: usize) {
+                self.raw.lower_to(val);
+            }
+            #[cfg(feature = "sanitizer")]
+            fn check_red_zone(&self, end: usize) {
+                self.raw.check_red_zone(end);
+            }
+            #[cfg(feature = "sanitizer")]
+            fn last_guarded_start(&self) -> Option<usize> {
+                self.raw.sanitizer.last_start()
+            }
+        }
+
+        impl<$($generics)*> $crate::lake::lake::mark::Marks for $ty {
+            #[inline(always)]
+            fn marks_mut(&mut self) -> &mut $crate::lake::lake::mark::MarkStack {
+                &mut self.raw.mark_stack
+            }
+        }
+
+        // Inherent methods win name lookup, so these forward instead of recursing.
+        impl<$($generics)*> $crate::lake::LakeAlloc for $ty {
+            type DynDroplet = $dyn_droplet;
+            #[inline(always)]
+            fn alloc<const N: usize>(&mut self) -> Option<$crate::lake::droplet::droplet::Droplet<N, Self>> {
+                self.alloc::<N>()
+            }
+            #[inline(always)]
+            fn alloc_dyn(&mut self, size: usize) -> Option<$dyn_droplet> {
+                self.alloc_dyn(size)
+            }
+            #[inline(always)]
+            fn peek<const N: usize>(&self) -> Option<&[u8; N]> {
+                self.peek::<N>()
+            }
+            #[inline(always)]
+            fn as_slice(&self) -> &[u8] {
+                self.as_slice()
+            }
+            #[inline(always)]
+            fn reset(&mut self) {
+                self.reset()
+            }
+        }
+
+        impl<$($generics)*> $crate::lake::LakeBuffer for $ty {
+            fn buf_ptr(&mut self) -> *mut u8 {
+                self.raw.buf_ptr()
+            }
+        }
+    };
+}
+
+pub(super) use raw_lake_api;
@@ -1,4 +1,6 @@
 use crate::lake::LakeMeta;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[must_use]
 pub struct SandboxGuard<'a, T: LakeMeta> {
@@ -16,20 +18,14 @@ impl<'a, T: LakeMeta> SandboxGuard<'a, T> {
     /// Commit changes made inside the sandbox.
     #[inline(always)]
     pub fn commit(mut self) {
-        let lake: &mut &mut T = self.lake.as_mut().unwrap();
-        let delta: usize = lake.offset() - self.base_offset;
-        lake.set_offset(self.base_offset + delta);
         self.committed = true;
     }
 
     /// Commit and get back the original `Lake`.
     #[inline(always)]
     pub fn commit_and_return(mut self) -> &'a mut T {
-        let lake: &mut T = self.lake.take().unwrap();
-        let delta: usize = lake.offset() - self.base_offset;
-        lake.set_offset(self.base_offset + delta);
         self.committed = true;
-        lake
+        self.lake.take().unwrap()
     }
 }
 
@@ -42,3 +38,131 @@ impl<'a, T: LakeMeta> Drop for SandboxGuard<'a, T> {
         }
     }
 }
+
+static NEXT_MULTI_SANDBOX: AtomicUsize = AtomicUsize::new(1);
+
+/// Typed handle to a lake inside a `MultiSandbox`, handed out by `MultiSandbox::add`.
+#[derive(Debug)]
+pub struct SandboxSlot<T> {
+    sandbox: usize,
+    index: usize,
+    _lake: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for SandboxSlot<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SandboxSlot<T> {}
+
+impl<T> SandboxSlot<T> {
+    /// Position of the lake inside its sandbox.
+    #[inline(always)]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// One sandbox over several lakes at once: they commit together or roll back together.
+///
+/// Every lake's water level is remembered when it joins. Dropping the sandbox without `commit`
+/// puts all of them back, newest first — so a header `Lake` and a body `LakeView` never end up
+/// half rolled back.
+#[must_use]
+pub struct MultiSandbox<'a> {
+    id: usize,
+    lakes: Vec<(&'a mut (dyn LakeMeta + 'a), usize)>,
+    committed: bool,
+}
+
+impl Default for MultiSandbox<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> MultiSandbox<'a> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            id: NEXT_MULTI_SANDBOX.fetch_add(1, Ordering::Relaxed),
+            lakes: Vec::new(),
+            committed: false,
+        }
+    }
+    /// Sandbox all of `lakes` at their current water levels.
+    pub fn from_lakes<I>(lakes: I) -> Self
+    where
+        I: IntoIterator<Item = &'a mut (dyn LakeMeta + 'a)>,
+    {
+        let mut sandbox: Self = Self::new();
+        for lake in lakes {
+            sandbox.push(lake);
+        }
+        sandbox
+    }
+    /// Add a lake by trait object and return its index.
+    #[inline(always)]
+    pub fn push(&mut self, lake: &'a mut (dyn LakeMeta + 'a)) -> usize {
+        let base_offset: usize = lake.offset();
+        self.lakes.push((lake, base_offset));
+        self.lakes.len() - 1
+    }
+    /// Add a lake and get a typed slot back, so `get` can hand out the lake itself.
+    #[inline(always)]
+    pub fn add<T: LakeMeta + 'a>(&mut self, lake: &'a mut T) -> SandboxSlot<T> {
+        let index: usize = self.push(lake);
+        SandboxSlot {
+            sandbox: self.id,
+            index,
+            _lake: PhantomData,
+        }
+    }
+    /// The lake behind `slot`. Panics if the slot came from another sandbox.
+    #[inline(always)]
+    pub fn get<T: LakeMeta + 'a>(&mut self, slot: SandboxSlot<T>) -> &mut T {
+        assert_eq!(slot.sandbox, self.id, "SandboxSlot used with another MultiSandbox");
+        let lake: *mut (dyn LakeMeta + 'a) = &mut *self.lakes[slot.index].0;
+        // SAFETY: slots of this sandbox are only made by `add`, which pushed a `&mut T` at `index`.
+        unsafe { &mut *(lake as *mut T) }
+    }
+    /// The lake at `index`, as a trait object.
+    #[inline(always)]
+    pub fn lake(&mut self, index: usize) -> &mut (dyn LakeMeta + 'a) {
+        &mut *self.lakes[index].0
+    }
+    /// Water level the lake at `index` rolls back to.
+    #[inline(always)]
+    pub fn base_offset(&self, index: usize) -> usize {
+        self.lakes[index].1
+    }
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.lakes.len()
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.lakes.is_empty()
+    }
+    /// Keep what was allocated in every lake.
+    #[inline(always)]
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+    /// Roll every lake back. Same as dropping the sandbox.
+    #[inline(always)]
+    pub fn rollback(self) {}
+}
+
+impl Drop for MultiSandbox<'_> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        for (lake, base_offset) in self.lakes.iter_mut().rev() {
+            lake.set_offset(*base_offset);
+        }
+    }
+}
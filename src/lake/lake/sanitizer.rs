@@ -0,0 +1,101 @@
+use std::backtrace::Backtrace;
+use std::sync::Arc;
+
+/// Bytes of canary left after every allocation while the sanitizer is on.
+pub const RED_ZONE: usize = 16;
+/// What a red zone is filled with. Anything else in there means someone wrote past their droplet.
+pub const CANARY: u8 = 0xCA;
+/// What rewound water is filled with, so stale reads stand out in a hex dump.
+pub const POISON: u8 = 0xDD;
+
+#[derive(Clone)]
+struct RedZone {
+    /// Where the guarded allocation starts.
+    start: usize,
+    /// Where it ends, and the canary begins.
+    end: usize,
+    /// Where the canary ends. Short of `end + RED_ZONE` only at the very end of the lake.
+    zone_end: usize,
+    backtrace: Arc<Backtrace>,
+}
+
+/// Red zones behind a lake's allocations, oldest first. Only compiled with the `sanitizer` feature.
+///
+/// While enabled, every allocation is followed by `RED_ZONE` bytes of `CANARY`, and water given back
+/// is filled with `POISON`. Canaries are checked on every rollback and whenever a droplet is
+/// accessed; a trampled one panics with the offending allocation and where it was made.
+#[derive(Clone, Default)]
+pub(crate) struct Sanitizer {
+    enabled: bool,
+    zones: Vec<RedZone>,
+}
+
+impl Sanitizer {
+    #[inline(always)]
+    pub(crate) fn set_enabled(&mut self, state: bool) {
+        self.enabled = state;
+        self.zones.clear();
+    }
+    #[inline(always)]
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    /// Guard the allocation `start..end` and return where the lake's water level goes next.
+    pub(crate) fn after_alloc(&mut self, buf: &mut [u8], start: usize, end: usize) -> usize {
+        if !self.enabled {
+            return end;
+        }
+        let zone_end: usize = end.saturating_add(RED_ZONE).min(buf.len());
+        buf[end..zone_end].fill(CANARY);
+        self.zones.push(RedZone {
+            start,
+            end,
+            zone_end,
+            // Not `capture`: a report without the allocation site is half a report, whatever RUST_BACKTRACE says.
+            backtrace: Arc::new(Backtrace::force_capture()),
+        });
+        zone_end
+    }
+    /// Where the newest guarded allocation starts, if there is one.
+    #[inline(always)]
+    pub(crate) fn last_start(&self) -> Option<usize> {
+        self.zones.last().map(|zone| zone.start)
+    }
+    /// Check the canary behind the allocation ending at `end`, if it has one.
+    pub(crate) fn check_zone(&self, buf: &[u8], end: usize) {
+        if !self.enabled {
+            return;
+        }
+        if let Ok(i) = self.zones.binary_search_by_key(&end, |zone| zone.end) {
+            Self::check(buf, &self.zones[i]);
+        }
+    }
+    /// Check every canary, then poison everything from `offset` up to `top` and forget the zones there.
+    pub(crate) fn before_rewind(&mut self, buf: &mut [u8], offset: usize, top: usize, poison: bool) {
+        if !self.enabled {
+            return;
+        }
+        for zone in &self.zones {
+            Self::check(buf, zone);
+        }
+        let keep: usize = self.zones.partition_point(|zone| zone.zone_end <= offset);
+        self.zones.truncate(keep);
+        if poison && top > offset {
+            buf[offset..top].fill(POISON);
+        }
+    }
+    fn check(buf: &[u8], zone: &RedZone) {
+        let canary: &[u8] = &buf[zone.end..zone.zone_end];
+        if let Some(hit) = canary.iter().position(|byte| *byte != CANARY) {
+            panic!(
+                "lake sanitizer: allocation {}..{} ({} bytes) was overrun, red zone byte at offset {} is {:#04x}\nallocated at:\n{}",
+                zone.start,
+                zone.end,
+                zone.end - zone.start,
+                zone.end + hit,
+                canary[hit],
+                zone.backtrace
+            );
+        }
+    }
+}
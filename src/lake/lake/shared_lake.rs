@@ -0,0 +1,236 @@
+use crate::lake::lake::lake::DynWater;
+use crate::lake::{LakeId, LakeMeta};
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Set in `state` while a reset owns the lake; the low bits count live droplets.
+const RESETTING: usize = 1 << (usize::BITS - 1);
+
+/// A `Lake` many threads can fill at once.
+///
+/// The bump offset is an `AtomicUsize`, so `alloc`/`alloc_dyn` work through `&self`:
+/// claiming a stretch of water is a single `fetch_add`, no mutex, no `&mut`.
+/// Every droplet is a disjoint slice, so threads never step on each other's bytes.
+///
+/// Resetting is epoch-based. Each live `SharedDroplet` is counted; `reset` closes the lake
+/// to new allocations, waits until the last droplet is gone, then drains it and starts a new
+/// generation. Allocations that arrive during a reset simply wait for it to finish.
+/// Holding a droplet while calling `reset` on the same thread would wait forever —
+/// use `try_reset` where that can happen.
+///
+/// Allocations are byte-aligned. A failed allocation still consumes its bytes from the offset,
+/// which is harmless: the lake was full anyway, and `reset` gives everything back.
+pub struct SharedLake<const SIZE: usize> {
+    /// Our "water reservoir", shared between all threads and aligned to `LAKE_ALIGN`.
+    buf: NonNull<u8>,
+    /// Current fill level. May run past `SIZE` after failed allocations.
+    offset: AtomicUsize,
+    /// Live droplets, plus the `RESETTING` bit.
+    state: AtomicUsize,
+    /// Generation counter, bumped by every reset.
+    generation: AtomicUsize,
+    /// Who this is, for `origin`.
+    id: LakeId,
+    /// Wipe data with 0u8 while reset
+    zeroing: bool,
+    _marker: PhantomData<Box<[u8]>>,
+}
+
+// The buffer is only ever handed out in disjoint slices, guarded by `state`.
+unsafe impl<const SIZE: usize> Send for SharedLake<SIZE> {}
+unsafe impl<const SIZE: usize> Sync for SharedLake<SIZE> {}
+
+/// A slice of a `SharedLake`, owned by one thread until it's dropped.
+///
+/// Borrows the lake, so it can't outlive it; while it's alive, `reset` waits.
+#[must_use]
+pub struct SharedDroplet<'a, T: ?Sized> {
+    data: &'a mut T,
+    state: &'a AtomicUsize,
+}
+
+impl<const SIZE: usize> SharedLake<SIZE> {
+    /// Create a new, pristine shared lake.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            buf: DynWater::new(SIZE).into_raw(),
+            offset: AtomicUsize::new(0),
+            state: AtomicUsize::new(0),
+            generation: AtomicUsize::new(0),
+            id: LakeId::default(),
+            zeroing: false,
+            _marker: PhantomData,
+        }
+    }
+    /// Register a new borrower, waiting out a reset in progress.
+    #[inline(always)]
+    fn enter(&self) {
+        while self.state.fetch_add(1, Ordering::Acquire) & RESETTING != 0 {
+            self.state.fetch_sub(1, Ordering::Relaxed);
+            while self.state.load(Ordering::Relaxed) & RESETTING != 0 {
+                std::hint::spin_loop();
+            }
+        }
+    }
+    /// Claim `size` bytes with a single `fetch_add`.
+    #[inline(always)]
+    fn claim(&self, size: usize) -> Option<*mut u8> {
+        if size > SIZE {
+            return None;
+        }
+        self.enter();
+        let start: usize = self.offset.fetch_add(size, Ordering::Relaxed);
+        if start > SIZE - size {
+            self.state.fetch_sub(1, Ordering::Release);
+            return None;
+        }
+        Some(unsafe { self.buf.as_ptr().add(start) })
+    }
+    /// Allocate a fixed-size droplet. Safe to call from many threads at once.
+    #[inline(always)]
+    pub fn alloc<const N: usize>(&self) -> Option<SharedDroplet<'_, [u8; N]>> {
+        let ptr: *mut [u8; N] = self.claim(N)? as *mut [u8; N];
+        Some(SharedDroplet {
+            data: unsafe { &mut *ptr },
+            state: &self.state,
+        })
+    }
+    /// Allocate a runtime-sized droplet. Safe to call from many threads at once.
+    #[inline(always)]
+    pub fn alloc_dyn(&self, size: usize) -> Option<SharedDroplet<'_, [u8]>> {
+        let ptr: *mut u8 = self.claim(size)?;
+        Some(SharedDroplet {
+            data: unsafe { std::slice::from_raw_parts_mut(ptr, size) },
+            state: &self.state,
+        })
+    }
+    /// Drain the lake once no droplet is alive. Returns `false` instead of waiting.
+    #[inline(always)]
+    pub fn try_reset(&self) -> bool {
+        if self
+            .state
+            .compare_exchange(0, RESETTING, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return false;
+        }
+        let used: usize = self.used();
+        if self.zeroing {
+            unsafe { std::ptr::write_bytes(self.buf.as_ptr(), 0, used) };
+        }
+        self.offset.store(0, Ordering::Relaxed);
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        // Not `store(0)`: allocations bouncing off the reset may still be counted in here.
+        self.state.fetch_and(!RESETTING, Ordering::Release);
+        true
+    }
+    /// Wait for every outstanding droplet to be dropped, then drain the lake and start a new generation.
+    #[inline(always)]
+    pub fn reset(&self) {
+        while !self.try_reset() {
+            std::thread::yield_now();
+        }
+    }
+    /// Returns used capacity.
+    #[inline(always)]
+    pub fn used(&self) -> usize {
+        self.offset.load(Ordering::Relaxed).min(SIZE)
+    }
+    /// Returns remaining capacity.
+    #[inline(always)]
+    pub fn remaining(&self) -> usize {
+        SIZE - self.used()
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.used() == 0
+    }
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.used() == SIZE
+    }
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        SIZE
+    }
+    #[inline(always)]
+    pub fn generation(&self) -> usize {
+        self.generation.load(Ordering::Relaxed)
+    }
+    /// Number of droplets currently alive.
+    #[inline(always)]
+    pub fn borrowers(&self) -> usize {
+        self.state.load(Ordering::Relaxed) & !RESETTING
+    }
+    /// Everything written so far. Needs `&mut`, so no thread can be writing.
+    #[inline(always)]
+    pub fn as_slice(&mut self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.buf.as_ptr(), self.used()) }
+    }
+    #[inline(always)]
+    pub fn set_zeroing(&mut self, state: bool) {
+        self.zeroing = state;
+    }
+    #[inline(always)]
+    pub fn get_zeroing(&self) -> bool {
+        self.zeroing
+    }
+}
+
+impl<const SIZE: usize> Default for SharedLake<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE: usize> Drop for SharedLake<SIZE> {
+    fn drop(&mut self) {
+        drop(unsafe { DynWater::from_raw(self.buf, SIZE) });
+    }
+}
+
+impl<const SIZE: usize> LakeMeta for SharedLake<SIZE> {
+    fn offset(&self) -> usize {
+        self.used()
+    }
+    fn offset_mut(&mut self) -> &mut usize {
+        let offset: &mut usize = self.offset.get_mut();
+        *offset = (*offset).min(SIZE);
+        offset
+    }
+    fn generation(&self) -> usize {
+        self.generation()
+    }
+    fn capacity(&self) -> usize {
+        SIZE
+    }
+    fn origin(&self) -> usize {
+        self.id.get()
+    }
+}
+
+impl<T: ?Sized> Deref for SharedDroplet<'_, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        self.data
+    }
+}
+
+impl<T: ?Sized> DerefMut for SharedDroplet<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        self.data
+    }
+}
+
+impl<T: ?Sized> Drop for SharedDroplet<'_, T> {
+    fn drop(&mut self) {
+        self.state.fetch_sub(1, Ordering::Release);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::lake::{LakeMeta, LakeSnapshot};
+use std::ops::{Deref, DerefMut};
+
+/// A snapshot that rewinds the lake by itself when it goes out of scope.
+///
+/// Allocate through the guard (it derefs to the lake); when it's dropped, everything carved
+/// since it was taken drains away. `keep` disarms it and leaves the water where it is.
+/// If the lake was reset or rewound below the snapshot in the meantime, the guard
+/// has nothing left to restore and quietly does nothing.
+#[must_use]
+pub struct SnapshotGuard<'a, T: LakeMeta> {
+    lake: &'a mut T,
+    snapshot: LakeSnapshot,
+    armed: bool,
+}
+
+impl<'a, T: LakeMeta> SnapshotGuard<'a, T> {
+    #[inline(always)]
+    pub(crate) fn new(lake: &'a mut T) -> Self {
+        let snapshot: LakeSnapshot = lake.snapshot();
+        Self {
+            lake,
+            snapshot,
+            armed: true,
+        }
+    }
+    /// The snapshot this guard will rewind to.
+    #[inline(always)]
+    pub fn snapshot(&self) -> LakeSnapshot {
+        self.snapshot
+    }
+    /// Keep everything allocated since the snapshot.
+    #[inline(always)]
+    pub fn keep(mut self) {
+        self.armed = false;
+    }
+}
+
+impl<T: LakeMeta> Deref for SnapshotGuard<'_, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        self.lake
+    }
+}
+
+impl<T: LakeMeta> DerefMut for SnapshotGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        self.lake
+    }
+}
+
+impl<T: LakeMeta> Drop for SnapshotGuard<'_, T> {
+    fn drop(&mut self) {
+        if self.armed {
+            let _ = self.lake.try_rewind(self.snapshot);
+        }
+    }
+}
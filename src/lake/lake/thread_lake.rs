@@ -1,27 +1,105 @@
 use std::cell::RefCell;
+use std::thread::LocalKey;
 use crate::lake::lake::lake::Lake;
 
 pub const DEFAULT_SIZE: usize = 65536;
 
 thread_local! {
-    pub static THREAD_LAKE: RefCell<Option<Lake<DEFAULT_SIZE>>> = RefCell::new(None);
+    pub static THREAD_LAKE: RefCell<Option<Lake<DEFAULT_SIZE>>> = const { RefCell::new(None) };
 }
 
+/// The lake behind `with_lake!` and `try_with_lake!`.
+pub static DEFAULT_LAKE: ThreadLake<DEFAULT_SIZE> = ThreadLake::new(&THREAD_LAKE);
+
+/// Fill (or refill) this thread's default lake with fresh water.
 pub fn thread_lake_init() {
-    THREAD_LAKE.with(|slot| {
-        *slot.borrow_mut() = Some(Lake::new());
-    });
+    DEFAULT_LAKE.init();
+}
+
+/// A handle to a per-thread lake of any size.
+///
+/// Every thread gets its own `Lake<SIZE>`, created on first use — no init call required.
+/// Declare as many as you like with `thread_lake!`, so each subsystem gets its own
+/// scratch water instead of fighting over one shared 64 KiB pond.
+pub struct ThreadLake<const SIZE: usize> {
+    key: &'static LocalKey<RefCell<Option<Lake<SIZE>>>>,
+}
+
+impl<const SIZE: usize> ThreadLake<SIZE> {
+    /// Wrap a thread-local slot. Usually done for you by `thread_lake!`.
+    #[inline(always)]
+    pub const fn new(key: &'static LocalKey<RefCell<Option<Lake<SIZE>>>>) -> Self {
+        Self { key }
+    }
+    /// Replace this thread's lake with a pristine one.
+    #[inline(always)]
+    pub fn init(&self) {
+        self.key.with(|slot| {
+            *slot.borrow_mut() = Some(Lake::new());
+        });
+    }
+    /// Run `f` on this thread's lake, creating it on first use.
+    /// Panics if the lake is already borrowed by an outer `with` on the same thread.
+    #[inline(always)]
+    pub fn with<R>(&self, f: impl FnOnce(&mut Lake<SIZE>) -> R) -> R {
+        self.key.with(|slot| f(slot.borrow_mut().get_or_insert_with(Lake::new)))
+    }
+    /// Like `with`, but returns `None` instead of panicking when the lake is already
+    /// borrowed on this thread or the thread is shutting down.
+    #[inline(always)]
+    pub fn try_with<R>(&self, f: impl FnOnce(&mut Lake<SIZE>) -> R) -> Option<R> {
+        self.key
+            .try_with(|slot| {
+                let mut slot = slot.try_borrow_mut().ok()?;
+                Some(f(slot.get_or_insert_with(Lake::new)))
+            })
+            .ok()
+            .flatten()
+    }
+    /// Has this thread's lake been created yet?
+    #[inline(always)]
+    pub fn is_initialized(&self) -> bool {
+        self.key.with(|slot| slot.borrow().is_some())
+    }
+    /// Drop this thread's lake, giving its memory back. The next use creates a new one.
+    #[inline(always)]
+    pub fn take(&self) -> Option<Lake<SIZE>> {
+        self.key.with(|slot| slot.borrow_mut().take())
+    }
+}
+
+/// Declare thread-local lakes of any size:
+///
+/// `thread_lake! { pub static SCRATCH: 4096; static JSON: 1 << 20; }`
+///
+/// Each becomes a `ThreadLake<SIZE>`; use it with `SCRATCH.with(|lake| ...)`.
+#[macro_export]
+macro_rules! thread_lake {
+    ($($(#[$attr:meta])* $vis:vis static $name:ident: $size:expr;)+) => {
+        $(
+            $(#[$attr])*
+            $vis static $name: $crate::thread_lake::ThreadLake<{ $size }> = {
+                ::std::thread_local! {
+                    static LAKE: ::std::cell::RefCell<::std::option::Option<$crate::Lake<{ $size }>>> =
+                        const { ::std::cell::RefCell::new(::std::option::Option::None) };
+                }
+                $crate::thread_lake::ThreadLake::new(&LAKE)
+            };
+        )+
+    };
 }
 
 #[macro_export]
 macro_rules! with_lake {
     ($block:expr) => {{
-        $crate::thread_lake::THREAD_LAKE.with(|slot| {
-            let mut opt = slot.borrow_mut();
-            let lake = opt
-                .as_mut()
-                .expect("Lake not initialized. Call `thread_lake_init()` first.");
-            $block(lake)
-        })
+        $crate::thread_lake::DEFAULT_LAKE.with(|lake| $block(lake))
+    }};
+}
+
+/// Non-panicking `with_lake!`: `None` if the lake is already borrowed on this thread.
+#[macro_export]
+macro_rules! try_with_lake {
+    ($block:expr) => {{
+        $crate::thread_lake::DEFAULT_LAKE.try_with(|lake| $block(lake))
     }};
 }
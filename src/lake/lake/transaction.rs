@@ -0,0 +1,87 @@
+use crate::lake::lake::mark::MarkStack;
+use crate::lake::LakeMeta;
+use std::ops::{Deref, DerefMut};
+
+/// An all-or-nothing scope over a lake, opened by `LakeSandboxExt::transaction` or `savepoint`.
+///
+/// Allocate through it as usual — it derefs to the lake. Unless it is committed, dropping it
+/// (on `Err`, on an early return, or while a panic unwinds) puts the lake back as it was:
+/// water level and mark stack. Tracked values carved inside are dropped on the way.
+///
+/// The generation never goes back. If the transaction `reset` the lake, rollback moves it one
+/// past every generation used inside, so droplets from inside — and from before — stay invalid
+/// instead of coming back to life once the numbers are reused. The water can't be rolled back
+/// either: bytes a reset zeroed, or that were overwritten afterwards, stay that way.
+#[must_use]
+pub struct Transaction<'a, T: LakeMeta> {
+    lake: &'a mut T,
+    offset: usize,
+    generation: usize,
+    marks: Option<MarkStack>,
+    committed: bool,
+}
+
+impl<'a, T: LakeMeta> Transaction<'a, T> {
+    #[inline(always)]
+    pub(crate) fn begin(lake: &'a mut T) -> Self {
+        let marks: Option<MarkStack> = lake.mark_stack().map(|marks| marks.clone());
+        Self {
+            offset: lake.offset(),
+            generation: lake.generation(),
+            marks,
+            lake,
+            committed: false,
+        }
+    }
+    /// Open a nested transaction. Rolling it back only undoes what happened since the savepoint;
+    /// committing it hands its changes to this one, which can still roll them back.
+    #[inline(always)]
+    pub fn savepoint(&mut self) -> Transaction<'_, T> {
+        Transaction::begin(&mut *self.lake)
+    }
+    /// Keep everything done inside the transaction.
+    #[inline(always)]
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+    /// Undo everything done inside the transaction. Same as dropping it.
+    #[inline(always)]
+    pub fn rollback(self) {}
+    /// Water level the transaction rolls back to.
+    #[inline(always)]
+    pub fn base_offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<T: LakeMeta> Deref for Transaction<'_, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        self.lake
+    }
+}
+
+impl<T: LakeMeta> DerefMut for Transaction<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        self.lake
+    }
+}
+
+impl<T: LakeMeta> Drop for Transaction<'_, T> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        if self.lake.generation() != self.generation
+            && let Some(generation) = self.lake.generation_mut()
+        {
+            *generation += 1;
+        }
+        self.lake.set_offset(self.offset);
+        if let (Some(saved), Some(marks)) = (self.marks.take(), self.lake.mark_stack()) {
+            marks.restore(saved);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::io;
+
+#[cfg(unix)]
+unsafe extern "C" {
+    fn mlock(addr: *const std::ffi::c_void, len: usize) -> std::ffi::c_int;
+    fn munlock(addr: *const std::ffi::c_void, len: usize) -> std::ffi::c_int;
+}
+
+/// Zero `buf` with volatile writes, so the optimizer can't skip it even if nobody reads the bytes again.
+#[inline(never)]
+pub fn secure_zero(buf: &mut [u8]) {
+    let ptr: *mut u8 = buf.as_mut_ptr();
+    for i in 0..buf.len() {
+        unsafe { ptr.add(i).write_volatile(0) };
+    }
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+/// An `mlock`ed range, unlocked again on drop. Clones start unlocked: the copy lives in other memory.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryLock {
+    addr: usize,
+    len: usize,
+}
+
+impl MemoryLock {
+    /// Keep `buf` out of swap for as long as this lock lives.
+    pub(crate) fn lock(&mut self, buf: &[u8]) -> io::Result<()> {
+        if self.is_locked() || buf.is_empty() {
+            return Ok(());
+        }
+        #[cfg(unix)]
+        {
+            if unsafe { mlock(buf.as_ptr().cast(), buf.len()) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            self.addr = buf.as_ptr() as usize;
+            self.len = buf.len();
+            Ok(())
+        }
+        #[cfg(not(unix))]
+        {
+            Err(io::Error::new(io::ErrorKind::Unsupported, "mlock is not available on this platform"))
+        }
+    }
+    #[inline(always)]
+    pub(crate) fn is_locked(&self) -> bool {
+        self.len != 0
+    }
+}
+
+impl Clone for MemoryLock {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl Drop for MemoryLock {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if self.is_locked() {
+            unsafe { munlock(self.addr as *const std::ffi::c_void, self.len) };
+        }
+    }
+}
@@ -1,4 +1,9 @@
+mod lock;
 pub mod void;
 mod writer;
 
+pub(crate) use lock::MemoryLock;
+pub use lock::secure_zero;
+pub use writer::CopyPath;
+
 pub struct LakeTools;
\ No newline at end of file
@@ -1,20 +1,98 @@
-use std::arch::x86_64::{__m128i, __m256i, _mm256_loadu_si256, _mm256_storeu_si256, _mm_loadu_si128, _mm_prefetch, _mm_storeu_si128, _MM_HINT_T0};
+#[cfg(target_arch = "x86")]
+use std::arch::x86::{__m128i, __m256i, __m512i, _mm256_loadu_si256, _mm256_storeu_si256, _mm512_loadu_si512, _mm512_storeu_si512, _mm_loadu_si128, _mm_storeu_si128};
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::{__m128i, __m256i, __m512i, _mm256_loadu_si256, _mm256_storeu_si256, _mm512_loadu_si512, _mm512_storeu_si512, _mm_loadu_si128, _mm_storeu_si128};
+use std::sync::atomic::{AtomicU8, Ordering};
 use crate::lake::memory::LakeTools;
 
+/// The copy kernel used by `LakeTools::write_to`, widest first.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+#[repr(u8)]
+pub enum CopyPath {
+    /// Plain 8/4/1-byte moves. Runs everywhere.
+    Scalar = 1,
+    /// 16-byte moves (x86 / x86_64).
+    Sse2 = 2,
+    /// 32-byte moves (x86 / x86_64 with AVX2).
+    Avx2 = 3,
+    /// 64-byte moves (x86 / x86_64 with AVX-512F).
+    Avx512 = 4,
+}
+
+/// Detected once, on the first write. 0 means "not yet".
+static COPY_PATH: AtomicU8 = AtomicU8::new(0);
+
+impl CopyPath {
+    #[inline(always)]
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(CopyPath::Scalar),
+            2 => Some(CopyPath::Sse2),
+            3 => Some(CopyPath::Avx2),
+            4 => Some(CopyPath::Avx512),
+            _ => None,
+        }
+    }
+    /// The widest path this CPU can run.
+    fn detect() -> Self {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if std::arch::is_x86_feature_detected!("avx512f") {
+                return CopyPath::Avx512;
+            }
+            if std::arch::is_x86_feature_detected!("avx2") {
+                return CopyPath::Avx2;
+            }
+            if std::arch::is_x86_feature_detected!("sse2") {
+                return CopyPath::Sse2;
+            }
+        }
+        CopyPath::Scalar
+    }
+}
+
 impl LakeTools {
+    /// Which copy kernel `write_to` uses on this machine.
+    #[inline(always)]
+    pub fn copy_path() -> CopyPath {
+        if let Some(path) = CopyPath::from_u8(COPY_PATH.load(Ordering::Relaxed)) {
+            return path;
+        }
+        let path: CopyPath = CopyPath::detect();
+        COPY_PATH.store(path as u8, Ordering::Relaxed);
+        path
+    }
 
     //write_to(dst, src, N) finished in 9 ns.
     // -------------------------------------------
     // copy_nonoverlapping(dst, src, N) finished in 10 ns.
-    #[target_feature(enable = "avx2")]
-    pub unsafe fn write_to(mut dst: *mut u8, mut src: *const u8, mut len: usize) {
-        if len == 1024 {
-            Self::write_1024(dst, src);
-        } else {
-            Self::_write_to(dst, src, len);
+    /// Copy `len` bytes with the widest kernel the CPU supports (checked at runtime).
+    /// # Safety
+    /// `src` and `dst` must be valid for `len` bytes and must not overlap.
+    #[inline(always)]
+    pub unsafe fn write_to(dst: *mut u8, src: *const u8, len: usize) {
+        Self::write_to_using(Self::copy_path(), dst, src, len);
+    }
+
+    /// Copy with a specific kernel — or the widest supported one below it, if the CPU can't run it.
+    /// # Safety
+    /// Same as `write_to`.
+    #[inline(always)]
+    pub unsafe fn write_to_using(path: CopyPath, dst: *mut u8, src: *const u8, len: usize) {
+        match path.min(Self::copy_path()) {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            CopyPath::Avx512 => Self::_write_to_avx512(dst, src, len),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            CopyPath::Avx2 if len == 1024 => Self::write_1024(dst, src),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            CopyPath::Avx2 => Self::_write_to(dst, src, len),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            CopyPath::Sse2 => Self::_write_to_sse2(dst, src, len),
+            _ => Self::_write_to_scalar(dst, src, len),
         }
     }
 
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     #[target_feature(enable = "avx2")]
     pub(super) unsafe fn write_1024(dst: *mut u8, src: *const u8) {
         macro_rules! do32 {
@@ -58,6 +136,20 @@ impl LakeTools {
         do32!(992);
     }
 
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[target_feature(enable = "avx512f")]
+    pub(super) unsafe fn _write_to_avx512(mut dst: *mut u8, mut src: *const u8, mut len: usize) {
+        while len >= 64 {
+            let v: __m512i = _mm512_loadu_si512(src as *const __m512i);
+            _mm512_storeu_si512(dst as *mut __m512i, v);
+            src = src.add(64);
+            dst = dst.add(64);
+            len -= 64;
+        }
+        Self::_write_to(dst, src, len);
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     #[target_feature(enable = "avx2")]
     pub(super) unsafe fn _write_to(mut dst: *mut u8, mut src: *const u8, mut len: usize) {
         while len >= 32 {
@@ -67,6 +159,12 @@ impl LakeTools {
             dst = dst.add(32);
             len -= 32;
         }
+        Self::_write_to_sse2(dst, src, len);
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn _write_to_sse2(mut dst: *mut u8, mut src: *const u8, mut len: usize) {
         while len >= 16 {
             let v: __m128i = _mm_loadu_si128(src as *const __m128i);
             _mm_storeu_si128(dst as *mut __m128i, v);
@@ -74,6 +172,11 @@ impl LakeTools {
             dst = dst.add(16);
             len -= 16;
         }
+        Self::_write_to_scalar(dst, src, len);
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn _write_to_scalar(mut dst: *mut u8, mut src: *const u8, mut len: usize) {
         while len >= 8 {
             let val: u64 = core::ptr::read_unaligned(src as *const u64);
             core::ptr::write_unaligned(dst as *mut u64, val);
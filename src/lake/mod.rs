@@ -1,4 +1,13 @@
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::lake::droplet::droplet::Droplet;
+use crate::lake::droplet::droplet_ref::{DropletDynRef, DropletRef, DropletRefScope};
+use crate::lake::lake::drop_list::DropList;
+use crate::lake::lake::journal::JournalGuard;
 use crate::lake::lake::sandbox::SandboxGuard;
+use crate::lake::lake::snapshot_guard::SnapshotGuard;
+use crate::lake::lake::transaction::Transaction;
+use crate::lake::lake::mark::MarkStack;
 
 pub mod droplet;
 pub mod lake;
@@ -8,8 +17,153 @@ pub mod memory;
 pub use droplet::DropletBase;
 
 pub trait LakeAllocatorExt: LakeMeta {
-    fn alloc_struct<T>(&mut self) -> &mut T;
-    fn alloc_slice<T>(&mut self, count: usize) -> &mut [T];
+    /// Carve a `T` out of the lake, or explain why it doesn't fit.
+    fn try_alloc_struct<T>(&mut self) -> Result<&mut T, LakeError>;
+    /// Carve `count` consecutive `T`s out of the lake. The byte size is overflow-checked,
+    /// so a malformed length prefix yields an error instead of a wrapped-around allocation.
+    fn try_alloc_slice<T>(&mut self, count: usize) -> Result<&mut [T], LakeError>;
+    #[inline(always)]
+    fn alloc_struct<T>(&mut self) -> &mut T {
+        match self.try_alloc_struct() {
+            Ok(value) => value,
+            Err(err) => panic!("Lake overflow: {err}"),
+        }
+    }
+    #[inline(always)]
+    fn alloc_slice<T>(&mut self, count: usize) -> &mut [T] {
+        match self.try_alloc_slice(count) {
+            Ok(slice) => slice,
+            Err(err) => panic!("Lake overflow: {err}"),
+        }
+    }
+    /// Borrow-checked `alloc`: the droplet holds the lake's borrow, so it can't outlive
+    /// a `reset` or `rewind` — or the lake itself.
+    #[inline(always)]
+    fn alloc_ref<const N: usize>(&mut self) -> Option<DropletRef<'_, N>> {
+        let data: &mut [u8; N] = self.try_alloc_struct().ok()?;
+        Some(DropletRef::new(data))
+    }
+    /// Borrow-checked `alloc_dyn`.
+    #[inline(always)]
+    fn alloc_dyn_ref(&mut self, size: usize) -> Option<DropletDynRef<'_>> {
+        let data: &mut [u8] = self.try_alloc_slice(size).ok()?;
+        Some(DropletDynRef::new(data))
+    }
+    /// Borrow the lake once and carve as many borrow-checked droplets from it as fit.
+    #[inline(always)]
+    fn ref_scope(&mut self) -> DropletRefScope<'_, Self>
+    where
+        Self: Sized,
+    {
+        DropletRefScope::new(self)
+    }
+    /// Build a `T` right in the lake. Unlike `alloc_struct`, the memory is written
+    /// before you ever see it, so any `T` is fine — not just the ones happy with stale bytes.
+    #[inline(always)]
+    fn try_alloc_with<T, F>(&mut self, f: F) -> Result<&mut T, LakeError>
+    where
+        F: FnOnce() -> T,
+    {
+        let slot: &mut MaybeUninit<T> = self.try_alloc_struct()?;
+        Ok(slot.write(f()))
+    }
+    /// Move `value` into the lake.
+    #[inline(always)]
+    fn alloc_value<T>(&mut self, value: T) -> &mut T {
+        self.alloc_with(|| value)
+    }
+    #[inline(always)]
+    fn alloc_with<T, F>(&mut self, f: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        match self.try_alloc_with(f) {
+            Ok(value) => value,
+            Err(err) => panic!("Lake overflow: {err}"),
+        }
+    }
+    /// Fill `count` slots with `f(index)`, in order.
+    #[inline(always)]
+    fn alloc_slice_fill_with<T, F>(&mut self, count: usize, mut f: F) -> &mut [T]
+    where
+        F: FnMut(usize) -> T,
+    {
+        let slots: &mut [MaybeUninit<T>] = self.alloc_slice(count);
+        for (i, slot) in slots.iter_mut().enumerate() {
+            slot.write(f(i));
+        }
+        // SAFETY: every slot was written above.
+        unsafe { &mut *(slots as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+    /// Move `value` into the lake and run its `Drop` once the lake is reset or rewound past it
+    /// (sandbox rollbacks included), newest first. Use it for `String`, `Vec`, `Arc` and friends
+    /// that would otherwise leak.
+    ///
+    /// Lakes without a drop-list (`LakeView`, `SharedLake`) refuse with `LakeError::Untracked`
+    /// and take nothing. A split view's water belongs to its parent, which can reset and refill it
+    /// long before the view's destructors would run.
+    ///
+    /// The destructor may run long after this call, so `T` can't borrow anything:
+    ///
+    /// ```compile_fail
+    /// use lake::{lake::LakeAllocatorExt, Lake};
+    ///
+    /// struct Peek<'a>(&'a String);
+    /// impl Drop for Peek<'_> {
+    ///     fn drop(&mut self) {
+    ///         println!("{}", self.0);
+    ///     }
+    /// }
+    ///
+    /// let mut lake: Lake<64> = Lake::new();
+    /// {
+    ///     let s: String = String::from("gone");
+    ///     lake.alloc_tracked(Peek(&s)); // error: `s` does not live long enough
+    /// }
+    /// lake.reset();
+    /// ```
+    #[inline(always)]
+    fn try_alloc_tracked<T: Send + 'static>(&mut self, value: T) -> Result<&mut T, LakeError> {
+        if self.drops().is_none() {
+            return Err(LakeError::Untracked);
+        }
+        let slot: *mut MaybeUninit<T> = self.try_alloc_struct::<MaybeUninit<T>>()?;
+        #[allow(unused_mut)]
+        let mut offset: usize = self.offset() - size_of::<T>();
+        #[cfg(feature = "sanitizer")]
+        if let Some(start) = self.last_guarded_start() {
+            offset = start;
+        }
+        let ptr: *mut T = unsafe { (*slot).write(value) };
+        if let Some(drops) = self.drops() {
+            drops.push(offset, ptr);
+        }
+        Ok(unsafe { &mut *ptr })
+    }
+    #[inline(always)]
+    fn alloc_tracked<T: Send + 'static>(&mut self, value: T) -> &mut T {
+        match self.try_alloc_tracked(value) {
+            Ok(value) => value,
+            Err(err @ LakeError::Untracked) => panic!("{err}"),
+            Err(err) => panic!("Lake overflow: {err}"),
+        }
+    }
+    /// Copy a slice into the lake.
+    #[inline(always)]
+    fn alloc_slice_copy<T: Copy>(&mut self, src: &[T]) -> &mut [T] {
+        let slots: &mut [MaybeUninit<T>] = self.alloc_slice(src.len());
+        unsafe {
+            std::ptr::copy_nonoverlapping(src.as_ptr(), slots.as_mut_ptr() as *mut T, src.len());
+            &mut *(slots as *mut [MaybeUninit<T>] as *mut [T])
+        }
+    }
+    /// Copy a string into the lake.
+    #[inline(always)]
+    fn alloc_str(&mut self, src: &str) -> &mut str {
+        let bytes: &mut [u8] = self.alloc_slice_copy(src.as_bytes());
+        // SAFETY: the bytes came straight from a `str`.
+        unsafe { std::str::from_utf8_unchecked_mut(bytes) }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -24,11 +178,91 @@ pub trait LakeMeta {
     fn offset(&self) -> usize;
     fn offset_mut(&mut self) -> &mut usize;
     fn generation(&self) -> usize;
+    /// Move the water level. Tracked values at or above `val` are dropped first.
     #[inline(always)]
     fn set_offset(&mut self, val: usize) {
+        if let Some(drops) = self.drops() {
+            drops.release(val);
+        }
         *self.offset_mut() = val;
     }
+    /// Destructors registered through `alloc_tracked`. Lakes that can't keep them return `None`,
+    /// and `alloc_tracked` refuses to place values there.
+    #[inline(always)]
+    fn drops(&mut self) -> Option<&mut DropList> {
+        None
+    }
+    /// The `mark`/`reset_to_mark` stack, so transactions can put it back. `None` if the lake has none.
+    #[inline(always)]
+    fn mark_stack(&mut self) -> Option<&mut MarkStack> {
+        None
+    }
+    /// Writable generation counter, so a rolled-back transaction can undo a `reset`.
+    #[inline(always)]
+    fn generation_mut(&mut self) -> Option<&mut usize> {
+        None
+    }
+    /// Panic if the red zone behind the allocation ending at `end` was trampled.
+    /// Only lakes with sanitizer support check anything.
+    #[cfg(feature = "sanitizer")]
+    #[inline(always)]
+    fn check_red_zone(&self, _end: usize) {}
+    /// Where the newest allocation behind a red zone starts. `None` unless the lake is sanitized;
+    /// without red zones that start is simply the water level minus the allocation's size.
+    #[cfg(feature = "sanitizer")]
+    #[inline(always)]
+    fn last_guarded_start(&self) -> Option<usize> {
+        None
+    }
     fn capacity(&self) -> usize;
+    /// Identity of this lake (unique per lake, view and clone), so snapshots can't cross lakes.
+    /// `0` means unknown: snapshots are then checked by generation and offset only.
+    #[inline(always)]
+    fn origin(&self) -> usize {
+        0
+    }
+    /// Take a snapshot of the current water level, stamped with this lake's generation and identity.
+    #[inline(always)]
+    fn snapshot(&self) -> LakeSnapshot {
+        LakeSnapshot {
+            offset: self.offset(),
+            generation: self.generation(),
+            origin: self.origin(),
+        }
+    }
+    /// Rewind to `snapshot`, unless it belongs to another lake, an older generation,
+    /// or points above the current water level. Tracked values above it are dropped.
+    #[inline(always)]
+    fn try_rewind(&mut self, snapshot: LakeSnapshot) -> Result<(), LakeError> {
+        let origin: usize = self.origin();
+        if snapshot.origin != origin && snapshot.origin != 0 && origin != 0 {
+            return Err(LakeError::ForeignSnapshot {
+                origin: snapshot.origin,
+                lake: origin,
+            });
+        }
+        if snapshot.generation != self.generation() {
+            return Err(LakeError::StaleGeneration {
+                expected: snapshot.generation,
+                found: self.generation(),
+            });
+        }
+        if snapshot.offset > self.offset() {
+            return Err(LakeError::InvalidSnapshot {
+                offset: snapshot.offset,
+                current: self.offset(),
+            });
+        }
+        self.set_offset(snapshot.offset);
+        Ok(())
+    }
+    /// `try_rewind` that panics on a bad snapshot.
+    #[inline(always)]
+    fn rewind(&mut self, snapshot: LakeSnapshot) {
+        if let Err(err) = self.try_rewind(snapshot) {
+            panic!("{err}");
+        }
+    }
     fn stats(&self) -> LakeStats {
         LakeStats {
             used: self.offset(),
@@ -39,6 +273,56 @@ pub trait LakeMeta {
     }
 }
 
+/// Raw access to the contiguous buffer behind a lake.
+/// Lets generic code (like the `Allocator` handle) carve memory without knowing the lake type.
+pub trait LakeBuffer: LakeMeta {
+    /// Pointer to the first byte of the lake's buffer.
+    fn buf_ptr(&mut self) -> *mut u8;
+}
+
+/// The allocation API shared by every contiguous lake (`Lake`, `LakeView`, `DynLake`).
+///
+/// Write generic code against `impl LakeAlloc` and it behaves the same whichever lake it gets:
+/// the same bounds, the same rewind rules, the same droplets. Each droplet remembers the lake
+/// offset right after itself, and stops being valid once the lake rewinds below that point.
+pub trait LakeAlloc: LakeMeta {
+    /// Runtime-sized droplet handed out by `alloc_dyn`.
+    type DynDroplet: DropletBase;
+    /// Allocate a fixed-size droplet, or `None` if it doesn't fit.
+    fn alloc<const N: usize>(&mut self) -> Option<Droplet<N, Self>>
+    where
+        Self: Sized;
+    /// Allocate a runtime-sized droplet, or `None` if it doesn't fit.
+    fn alloc_dyn(&mut self, size: usize) -> Option<Self::DynDroplet>;
+    /// Look at the next `N` bytes without allocating them.
+    fn peek<const N: usize>(&self) -> Option<&[u8; N]>;
+    /// Everything allocated so far.
+    fn as_slice(&self) -> &[u8];
+    /// Drain the lake and start a new generation.
+    fn reset(&mut self);
+    /// Rewind by `n` bytes. Careful: not validated.
+    #[inline(always)]
+    fn reset_to(&mut self, n: usize) {
+        self.set_offset(self.offset().saturating_sub(n));
+    }
+    #[inline(always)]
+    fn used(&self) -> usize {
+        self.offset()
+    }
+    #[inline(always)]
+    fn remaining(&self) -> usize {
+        self.capacity() - self.offset()
+    }
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        self.offset() == 0
+    }
+    #[inline(always)]
+    fn is_full(&self) -> bool {
+        self.offset() == self.capacity()
+    }
+}
+
 pub trait LakeSandboxExt: LakeMeta {
     fn sandbox(&mut self) -> SandboxGuard<'_, Self>
     where
@@ -51,18 +335,169 @@ pub trait LakeSandboxExt: LakeMeta {
             committed: false,
         }
     }
+    /// Like `sandbox`, but rollback also restores committed bytes patched through the guard.
+    fn journaled_sandbox(&mut self) -> JournalGuard<'_, Self>
+    where
+        Self: LakeBuffer + Sized,
+    {
+        JournalGuard::new(self)
+    }
+    /// Run `f` as a transaction: `Ok` commits, `Err` — or a panic — rolls the lake back to
+    /// where it was: offset and marks. A `reset` inside still retires its generations, see `Transaction`. Nest with `tx.savepoint()`
+    /// or another `tx.transaction(..)`.
+    fn transaction<R, E, F>(&mut self, f: F) -> Result<R, E>
+    where
+        Self: Sized,
+        F: FnOnce(&mut Transaction<'_, Self>) -> Result<R, E>,
+    {
+        let mut tx: Transaction<'_, Self> = Transaction::begin(self);
+        let result: Result<R, E> = f(&mut tx);
+        if result.is_ok() {
+            tx.commit();
+        }
+        result
+    }
+    /// Snapshot the lake and rewind to it automatically when the guard is dropped.
+    fn snapshot_guard(&mut self) -> SnapshotGuard<'_, Self>
+    where
+        Self: Sized,
+    {
+        SnapshotGuard::new(self)
+    }
 }
 
 impl<T: LakeMeta> LakeSandboxExt for T {}
 
+/// Everything that can go wrong while swimming in a lake, with enough context to tell why.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum LakeError {
-    Overflow,
+    /// Not enough water left: `requested` bytes aligned to `align` don't fit into `remaining`
+    /// (out of `capacity`).
+    Overflow {
+        requested: usize,
+        align: usize,
+        remaining: usize,
+        capacity: usize,
+    },
+    /// The byte size of `count` elements of `size` bytes each doesn't fit into `usize`.
+    SizeOverflow { size: usize, count: usize },
+    /// Memory at `address` is not aligned to `align`.
+    Misaligned { address: usize, align: usize },
+    /// A droplet (or token) from generation `expected` was used after the lake moved on to `found`.
+    StaleGeneration { expected: usize, found: usize },
+    /// The lake was rewound to `current`, below an allocation at `offset`.
+    Rewound { offset: usize, current: usize },
+    /// A snapshot taken from lake `origin` was handed to lake `lake` (both `LakeMeta::origin` ids).
+    ForeignSnapshot { origin: usize, lake: usize },
+    /// A snapshot pointing at `offset` can't be restored while the lake is at `current`.
+    InvalidSnapshot { offset: usize, current: usize },
+    /// Mark `depth` doesn't exist: only `marks` marks are on the stack.
+    InvalidMark { depth: usize, marks: usize },
+    /// `len` bytes at `offset` reach past the `used` part of the lake.
+    OutOfBounds { offset: usize, len: usize, used: usize },
+    /// `alloc_tracked` on a lake that can't run destructors (no `LakeMeta::drops`).
+    Untracked,
+    /// The OS refused to `mlock` the lake's buffer.
+    MemoryLock(std::io::Error),
+    /// The closure passed to `try_process` reported a failure.
+    Process(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl std::fmt::Display for LakeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LakeError::Overflow { requested, align, remaining, capacity } => write!(
+                f,
+                "lake overflow: requested {requested} bytes (align {align}), but only {remaining} of {capacity} remain"
+            ),
+            LakeError::SizeOverflow { size, count } => {
+                write!(f, "allocation size overflow: {count} elements of {size} bytes")
+            }
+            LakeError::Misaligned { address, align } => {
+                write!(f, "address {address:#x} is not aligned to {align}")
+            }
+            LakeError::StaleGeneration { expected, found } => {
+                write!(f, "stale generation: expected {expected}, lake is at {found}")
+            }
+            LakeError::Rewound { offset, current } => write!(
+                f,
+                "lake was rewound to offset {current}, below an allocation at {offset}"
+            ),
+            LakeError::ForeignSnapshot { origin, lake } => {
+                write!(f, "snapshot from lake #{origin} used on lake #{lake}")
+            }
+            LakeError::InvalidSnapshot { offset, current } => {
+                write!(f, "invalid snapshot at offset {offset}, lake is at {current}")
+            }
+            LakeError::InvalidMark { depth, marks } => {
+                write!(f, "invalid mark at depth {depth}, only {marks} marks on the stack")
+            }
+            LakeError::OutOfBounds { offset, len, used } => {
+                write!(f, "{len} bytes at offset {offset} reach past the {used} bytes in use")
+            }
+            LakeError::Untracked => write!(f, "this lake can't track destructors"),
+            LakeError::MemoryLock(err) => write!(f, "could not lock lake memory: {err}"),
+            LakeError::Process(err) => write!(f, "process failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LakeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LakeError::Process(err) => Some(err.as_ref()),
+            LakeError::MemoryLock(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Byte size of a `[T; count]`, checked against `usize` overflow.
+#[inline(always)]
+pub(crate) fn slice_bytes<T>(count: usize) -> Result<usize, LakeError> {
+    let size: usize = size_of::<T>();
+    size.checked_mul(count)
+        .ok_or(LakeError::SizeOverflow { size, count })
+}
+
+static NEXT_LAKE_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// Identity of one lake or view, so snapshots can't cross between them.
+/// Every new lake, split view and clone draws a fresh one — two views over the same bytes
+/// are still two lakes.
+#[derive(Debug)]
+pub(crate) struct LakeId(usize);
+
+impl LakeId {
+    #[inline(always)]
+    pub(crate) fn get(&self) -> usize {
+        self.0
+    }
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+impl Default for LakeId {
+    #[inline(always)]
+    fn default() -> Self {
+        Self(NEXT_LAKE_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Clone for LakeId {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// A saved water level. Remembers which lake and which generation it came from,
+/// so `try_rewind` can refuse to restore it anywhere else.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub struct LakeSnapshot {
     pub offset: usize,
+    pub generation: usize,
+    /// `LakeMeta::origin` of the lake it was taken from.
+    pub origin: usize,
 }
 
 #[allow(dead_code)]
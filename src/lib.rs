@@ -3,7 +3,13 @@ pub mod lake;
 pub mod small_lake;
 
 pub use lake::lake::*;
-pub use lake::lake::lake::Lake;
-pub use lake::droplet::{droplet, droplet_dyn};
+pub use lake::lake::lake::{Lake, LAKE_ALIGN};
+pub use lake::lake::chained_lake::{ChainedLake, LakeGrowth};
+pub use lake::lake::dyn_lake::DynLake;
+pub use lake::lake::shared_lake::{SharedDroplet, SharedLake};
+pub use lake::lake::pool::{LakePool, LakePoolStats};
+#[cfg(feature = "allocator-api2")]
+pub use lake::lake::allocator::LakeHandle;
+pub use lake::droplet::{droplet, droplet_dyn, droplet_ref, scoped};
 pub use lake::droplet::DropletBase;
 pub use lake::utils;
\ No newline at end of file
@@ -1,4 +1,5 @@
 use crate::lake::memory::LakeTools;
+use std::{fmt, io};
 
 #[repr(C)]
 #[derive(Clone)]
@@ -97,6 +98,17 @@ impl<const N: usize> SmallLake<N> {
 
         self.pos += len;
     }
+    /// Copy as much of `src` as fits before the end of the buffer. Unlike `write`, never wraps:
+    /// streaming writers must not overwrite their own beginning. Returns the bytes written.
+    #[inline(always)]
+    pub fn write_partial(&mut self, src: &[u8]) -> usize {
+        let len: usize = src.len().min(N - self.pos);
+        if len > 0 {
+            unsafe { LakeTools::write_to(self.buf.as_mut_ptr().add(self.pos), src.as_ptr(), len) };
+            self.pos += len;
+        }
+        len
+    }
     #[allow(dead_code)]
     #[inline(always)]
     fn into_raw_parts(mut self) -> (*mut u8, usize) {
@@ -105,4 +117,31 @@ impl<const N: usize> SmallLake<N> {
         core::mem::forget(self);
         (ptr, len)
     }
-}
\ No newline at end of file
+}
+
+/// Streaming writes stop at the end of the buffer instead of wrapping around;
+/// a full lake reports `WriteZero` / `fmt::Error`. Call `reset_pos` to reuse it.
+impl<const N: usize> io::Write for SmallLake<N> {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.write_partial(buf) {
+            0 if !buf.is_empty() => Err(io::Error::new(io::ErrorKind::WriteZero, "small lake is full")),
+            written => Ok(written),
+        }
+    }
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Write for SmallLake<N> {
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.len() > N - self.pos {
+            return Err(fmt::Error);
+        }
+        self.write_partial(s.as_bytes());
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use lake::droplet_dyn::DropletDyn;
+use lake::lake::{LakeAllocatorExt, LakeSnapshot};
+use lake::lake_view::LakeView;
+use lake::{ChainedLake, DropletBase, DynLake, Lake, SharedLake, LAKE_ALIGN};
+use std::alloc::Layout;
+
+#[test]
+fn test_lake_buffer_starts_on_cache_line() {
+    for _ in 0..8 {
+        let lake: Lake<100> = Lake::<100>::new();
+        assert_eq!(lake.as_slice().as_ptr() as usize % LAKE_ALIGN, 0);
+    }
+}
+
+#[test]
+fn test_runtime_sized_buffers_start_on_cache_line() {
+    let lake: DynLake = DynLake::new(100);
+    assert_eq!(lake.as_slice().as_ptr() as usize % LAKE_ALIGN, 0);
+    assert_eq!(lake.capacity(), 100);
+
+    let mut shared: SharedLake<100> = SharedLake::<100>::new();
+    let first = shared.alloc_dyn(8).unwrap();
+    assert_eq!(first.as_ptr() as usize % LAKE_ALIGN, 0);
+    drop(first);
+    assert_eq!(shared.as_slice().as_ptr() as usize % LAKE_ALIGN, 0);
+
+    // Every chunk of a chain starts on a cache line, not just the first one.
+    let mut chained: ChainedLake<100> = ChainedLake::<100>::new();
+    let head: DropletDyn<100> = chained.alloc_dyn(90).unwrap();
+    let next: DropletDyn<100> = chained.alloc_dyn(90).unwrap();
+    assert_eq!(head.d_as_slice().as_ptr() as usize % LAKE_ALIGN, 0);
+    assert_eq!(next.d_as_slice().as_ptr() as usize % LAKE_ALIGN, 0);
+}
+
+#[test]
+fn test_alloc_aligned_skips_padding() {
+    let mut lake: Lake<256> = Lake::<256>::new();
+    let _ = lake.alloc::<3>().unwrap();
+
+    let droplet = lake.alloc_aligned::<32, 32>().unwrap();
+    assert_eq!(droplet.as_ptr() as usize % 32, 0);
+    assert!(droplet.is_valid());
+    assert_eq!(lake.used(), 64);
+
+    let wide = lake.alloc_aligned::<64, 64>().unwrap();
+    assert_eq!(wide.as_ptr() as usize % 64, 0);
+    assert_eq!(lake.used(), 128);
+
+    assert!(lake.alloc_aligned::<128, 64>().is_some());
+    assert!(lake.alloc_aligned::<1, 1>().is_none());
+}
+
+#[test]
+fn test_alloc_layout_page_aligned() {
+    let mut lake: Lake<{ 3 * 4096 }> = Lake::<{ 3 * 4096 }>::new();
+    let _ = lake.alloc::<1>().unwrap();
+    let snapshot: LakeSnapshot = lake.snapshot();
+
+    let layout: Layout = Layout::from_size_align(4096, 4096).unwrap();
+    let page: DropletDyn<{ 3 * 4096 }> = lake.alloc_layout(layout).unwrap();
+    assert_eq!(page.d_as_ptr() as usize % 4096, 0);
+    assert_eq!(page.d_len(), 4096);
+
+    lake.rewind(snapshot);
+    assert_eq!(lake.used(), 1);
+    assert!(!page.is_valid());
+}
+
+#[test]
+fn test_alloc_layout_overflow() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let _ = lake.alloc::<1>().unwrap();
+    assert!(lake.alloc_layout(Layout::from_size_align(64, 1).unwrap()).is_none());
+    assert!(lake.alloc_layout(Layout::from_size_align(32, 32).unwrap()).is_some());
+    assert!(lake.is_full());
+}
+
+#[test]
+fn test_view_aligns_by_address() {
+    let mut lake: Lake<512> = Lake::<512>::new();
+    let _ = lake.alloc::<5>().unwrap();
+    let mut view: LakeView<512> = lake.split(200).unwrap();
+    assert_ne!(view.buf as usize % 8, 0);
+
+    let droplet = view.alloc_aligned::<16, 32>().unwrap();
+    assert_eq!(droplet.as_ptr() as usize % 32, 0);
+    let simd: DropletDyn<512> = view.alloc_layout(Layout::new::<[u64; 4]>()).unwrap();
+    assert_eq!(simd.d_as_ptr() as usize % 8, 0);
+
+    let value: &mut u64 = view.alloc_value(7u64);
+    assert_eq!(value as *mut u64 as usize % 8, 0);
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "allocator-api2")]
+
+use allocator_api2::boxed::Box;
+use allocator_api2::vec::Vec;
+use lake::lake_view::LakeView;
+use lake::{DynLake, Lake, LakeHandle};
+
+#[test]
+fn test_vec_in_lake() {
+    let mut lake: Lake<1024> = Lake::<1024>::new();
+    {
+        let mut v: Vec<u32, LakeHandle<Lake<1024>>> = Vec::new_in(lake.allocator());
+        for i in 0..100 {
+            v.push(i);
+        }
+        assert_eq!(v.iter().sum::<u32>(), 4950);
+    }
+    lake.reset();
+    assert_eq!(lake.used(), 0);
+}
+
+#[test]
+fn test_vec_grows_in_place_at_top() {
+    let mut lake: Lake<1024> = Lake::<1024>::new();
+    let handle = lake.allocator();
+    let mut v: Vec<u8, _> = Vec::with_capacity_in(8, handle);
+    let ptr: *const u8 = v.as_ptr();
+    v.extend_from_slice(&[1u8; 64]);
+    assert_eq!(v.as_ptr(), ptr);
+    assert_eq!(v.len(), 64);
+}
+
+#[test]
+fn test_box_in_lake_and_alignment() {
+    let mut lake: Lake<256> = Lake::<256>::new();
+    let handle = lake.allocator();
+    let _byte = Box::new_in(1u8, handle);
+    let wide = Box::new_in(0xDEAD_BEEF_u64, handle);
+    assert_eq!(&*wide as *const u64 as usize % align_of::<u64>(), 0);
+    assert_eq!(*wide, 0xDEAD_BEEF);
+}
+
+#[test]
+fn test_allocator_overflow() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let mut v: Vec<u8, _> = Vec::new_in(lake.allocator());
+    assert!(v.try_reserve(128).is_err());
+    assert!(v.try_reserve(32).is_ok());
+}
+
+#[test]
+fn test_dealloc_top_rewinds() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    {
+        let handle = lake.allocator();
+        let a = Box::new_in([0u8; 16], handle);
+        let b = Box::new_in([0u8; 16], handle);
+        drop(b);
+        drop(a);
+    }
+    assert_eq!(lake.used(), 0);
+}
+
+#[test]
+fn test_hashbrown_in_view() {
+    let mut lake: Lake<4096> = Lake::<4096>::new();
+    let mut view: LakeView<4096> = lake.split(2048).unwrap();
+    {
+        let mut map = hashbrown::HashMap::new_in(view.allocator());
+        for i in 0..32u32 {
+            map.insert(i, i * 2);
+        }
+        assert_eq!(map[&7], 14);
+    }
+    assert!(view.used() > 0);
+    view.reset();
+    assert_eq!(view.used(), 0);
+}
+
+#[test]
+fn test_dyn_lake_allocator() {
+    let mut lake: DynLake = DynLake::new(512);
+    let mut v: Vec<u16, _> = Vec::new_in(lake.allocator());
+    v.extend_from_slice(&[1, 2, 3]);
+    assert_eq!(&v[..], &[1, 2, 3]);
+}
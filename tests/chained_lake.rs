@@ -0,0 +1,171 @@
+use lake::droplet::Droplet;
+use lake::lake::{LakeAllocatorExt, LakeError, LakeMeta, LakeSnapshot};
+use lake::{ChainedLake, DropletBase, LakeGrowth};
+
+#[test]
+fn test_chained_lake_grows_instead_of_overflowing() {
+    let mut lake: ChainedLake<64> = ChainedLake::<64>::new();
+    let _ = lake.alloc::<48>().unwrap();
+    assert_eq!(lake.chunk_count(), 1);
+    let mut droplet: Droplet<32, ChainedLake<64>> = lake.alloc::<32>().expect("should grow");
+    assert_eq!(lake.chunk_count(), 2);
+    assert_eq!(lake.capacity(), 64 + 128);
+    droplet.copy_from_slice(&[7u8; 32]);
+    assert_eq!(&*droplet, &[7u8; 32]);
+    // The second chunk starts at logical offset 64, the tail of the first one is skipped.
+    assert_eq!(lake.used(), 64 + 32);
+}
+
+#[test]
+fn test_chained_lake_oversized_allocation() {
+    let mut lake: ChainedLake<16> = ChainedLake::<16>::new();
+    let droplet = lake.alloc_dyn(1000).unwrap();
+    assert_eq!(droplet.d_len(), 1000);
+    assert!(lake.capacity() >= 16 + 1000);
+}
+
+#[test]
+fn test_chained_lake_step_growth() {
+    let mut lake: ChainedLake<32> = ChainedLake::<32>::with_growth(LakeGrowth::Step(32));
+    let _ = lake.alloc::<32>().unwrap();
+    let _ = lake.alloc::<32>().unwrap();
+    let _ = lake.alloc::<32>().unwrap();
+    assert_eq!(lake.chunk_count(), 3);
+    assert_eq!(lake.capacity(), 96);
+}
+
+#[test]
+fn test_chained_lake_snapshot_across_chunks() {
+    let mut lake: ChainedLake<32> = ChainedLake::<32>::new();
+    let _ = lake.alloc::<16>().unwrap();
+    let snap: LakeSnapshot = lake.snapshot();
+    let _ = lake.alloc::<64>().unwrap();
+    let _ = lake.alloc::<128>().unwrap();
+    assert_eq!(lake.chunk_count(), 3);
+    lake.rewind(snap);
+    assert_eq!(lake.used(), 16);
+    // Rewound chunks are reused, not reallocated.
+    let _ = lake.alloc::<64>().unwrap();
+    assert_eq!(lake.chunk_count(), 3);
+}
+
+#[test]
+fn test_chained_lake_mark_across_chunks() {
+    let mut lake: ChainedLake<32> = ChainedLake::<32>::new();
+    let _ = lake.alloc::<8>().unwrap();
+    let mark = lake.mark();
+    let _ = lake.alloc::<100>().unwrap();
+    lake.reset_to_mark(mark).unwrap();
+    assert_eq!(lake.used(), 8);
+    assert_eq!(lake.chunk_remaining(), 24);
+}
+
+#[test]
+fn test_chained_lake_droplet_generation_across_chunks() {
+    let mut lake: ChainedLake<16> = ChainedLake::<16>::new();
+    let first = lake.alloc::<8>().unwrap();
+    let second = lake.alloc::<32>().unwrap();
+    assert!(first.is_valid());
+    assert!(second.is_valid());
+    lake.reset();
+    assert!(!first.is_valid());
+    assert!(!second.is_valid());
+    assert_eq!(lake.generation(), 1);
+}
+
+#[test]
+fn test_chained_lake_process_moves_to_new_chunk() {
+    let mut lake: ChainedLake<16> = ChainedLake::<16>::new();
+    let droplet = lake
+        .process(|remaining| {
+            assert_eq!(remaining, 16);
+            vec![0xAB; 40]
+        })
+        .unwrap();
+    assert_eq!(droplet.d_as_slice(), &[0xAB; 40]);
+    assert_eq!(lake.chunk_count(), 2);
+}
+
+#[test]
+fn test_chained_lake_alloc_struct_alignment() {
+    let mut lake: ChainedLake<16> = ChainedLake::<16>::new();
+    let _ = lake.alloc::<3>().unwrap();
+    let value: &mut u64 = lake.alloc_struct();
+    assert_eq!(value as *mut u64 as usize % align_of::<u64>(), 0);
+    let slice: &mut [u32] = lake.alloc_slice(16);
+    slice.copy_from_slice(&[5; 16]);
+    assert_eq!(slice[15], 5);
+}
+
+#[test]
+fn test_chained_lake_shrink() {
+    let mut lake: ChainedLake<16> = ChainedLake::<16>::new();
+    let _ = lake.alloc::<64>().unwrap();
+    assert_eq!(lake.chunk_count(), 2);
+    lake.reset();
+    lake.shrink();
+    assert_eq!(lake.chunk_count(), 1);
+    assert_eq!(lake.capacity(), 16);
+}
+
+#[test]
+fn test_chained_lake_reset_zeroing() {
+    let mut lake: ChainedLake<16> = ChainedLake::<16>::new();
+    lake.set_zeroing(true);
+    let mut a = lake.alloc::<16>().unwrap();
+    a.copy_from_slice(&[1u8; 16]);
+    let mut b = lake.alloc::<16>().unwrap();
+    b.copy_from_slice(&[2u8; 16]);
+    let (pa, pb) = (a.d_as_ptr(), b.d_as_ptr());
+    lake.reset();
+    unsafe {
+        assert_eq!(std::slice::from_raw_parts(pa, 16), &[0u8; 16]);
+        assert_eq!(std::slice::from_raw_parts(pb, 16), &[0u8; 16]);
+    }
+}
+
+#[test]
+fn test_chained_lake_try_alloc_slice_checked_size() {
+    let mut lake: ChainedLake<16> = ChainedLake::<16>::new();
+    assert!(matches!(lake.try_alloc_slice::<u64>(usize::MAX), Err(LakeError::SizeOverflow { .. })));
+    assert_eq!(lake.chunk_count(), 1);
+}
+
+#[test]
+fn test_chained_lake_huge_length_at_nonzero_offset() {
+    let mut lake: ChainedLake<16> = ChainedLake::<16>::new();
+    let _ = lake.alloc_dyn(5).unwrap();
+    assert!(matches!(lake.try_alloc_slice::<u8>(usize::MAX - 2), Err(LakeError::Overflow { .. })));
+    assert!(lake.alloc_dyn(usize::MAX - 2).is_none());
+    assert_eq!(lake.offset(), 5);
+    assert_eq!(lake.chunk_count(), 1);
+}
+
+#[test]
+fn test_chained_process_in_place_links_chunk_when_full() {
+    let mut lake: ChainedLake<8> = ChainedLake::<8>::new();
+    let _ = lake.alloc::<8>().unwrap();
+    let droplet = lake
+        .process_in_place(|buf: &mut [u8]| {
+            buf[..3].copy_from_slice(b"xyz");
+            Ok::<_, std::io::Error>(3)
+        })
+        .unwrap();
+    assert_eq!(droplet.d_as_slice(), b"xyz");
+    assert_eq!(lake.chunk_count(), 2);
+    assert_eq!(lake.used(), 11);
+}
+
+#[test]
+fn test_chained_split_view_in_a_linked_chunk() {
+    let mut lake: ChainedLake<64> = ChainedLake::<64>::new();
+    {
+        let mut view = lake.split(4096).unwrap();
+        view.alloc::<8>().unwrap().copy_from_slice(&[1u8; 8]);
+        assert_eq!(view.used(), 8);
+    }
+    assert_eq!(lake.chunk_count(), 2);
+    lake.reset();
+    lake.shrink();
+    assert_eq!(lake.chunk_count(), 1);
+}
@@ -0,0 +1,135 @@
+use lake::lake::{LakeAllocatorExt, LakeError, LakeMeta, LakeSandboxExt};
+use lake::{ChainedLake, DynLake, Lake};
+use std::sync::{Arc, Mutex};
+
+/// Writes its id into the shared log when dropped.
+struct Noisy {
+    id: u32,
+    log: Arc<Mutex<Vec<u32>>>,
+}
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        self.log.lock().unwrap().push(self.id);
+    }
+}
+
+fn noisy(id: u32, log: &Arc<Mutex<Vec<u32>>>) -> Noisy {
+    Noisy { id, log: log.clone() }
+}
+
+#[test]
+fn test_reset_drops_in_lifo_order() {
+    let log: Arc<Mutex<Vec<u32>>> = Arc::default();
+    let mut lake: Lake<256> = Lake::<256>::new();
+    for id in 0..3 {
+        lake.alloc_tracked(noisy(id, &log));
+    }
+    assert!(log.lock().unwrap().is_empty());
+
+    lake.reset();
+
+    assert_eq!(*log.lock().unwrap(), vec![2, 1, 0]);
+    assert!(lake.drops().unwrap().is_empty());
+}
+
+#[test]
+fn test_rewind_drops_only_values_past_snapshot() {
+    let log: Arc<Mutex<Vec<u32>>> = Arc::default();
+    let mut lake: Lake<256> = Lake::<256>::new();
+    lake.alloc_tracked(noisy(0, &log));
+    let snapshot = lake.snapshot();
+    lake.alloc_tracked(noisy(1, &log));
+    lake.alloc_tracked(noisy(2, &log));
+
+    lake.rewind(snapshot);
+
+    assert_eq!(*log.lock().unwrap(), vec![2, 1]);
+    assert_eq!(lake.drops().unwrap().len(), 1);
+}
+
+#[test]
+fn test_reset_to_mark_drops_tracked_strings() {
+    let shared: Arc<()> = Arc::new(());
+    let mut lake: Lake<256> = Lake::<256>::new();
+    let mark = lake.mark();
+    let name: &mut String = lake.alloc_tracked(String::from("river"));
+    name.push_str(" bank");
+    lake.alloc_tracked(shared.clone());
+    assert_eq!(Arc::strong_count(&shared), 2);
+
+    lake.reset_to_mark(mark).unwrap();
+
+    assert_eq!(Arc::strong_count(&shared), 1);
+    assert_eq!(lake.used(), 0);
+}
+
+#[test]
+fn test_sandbox_rollback_and_commit() {
+    let shared: Arc<()> = Arc::new(());
+    let mut lake: Lake<256> = Lake::<256>::new();
+    {
+        let mut sandbox = lake.sandbox();
+        sandbox.view().alloc_tracked(shared.clone());
+        assert_eq!(Arc::strong_count(&shared), 2);
+    }
+    assert_eq!(Arc::strong_count(&shared), 1);
+
+    let mut sandbox = lake.sandbox();
+    sandbox.view().alloc_tracked(shared.clone());
+    sandbox.commit();
+    assert_eq!(Arc::strong_count(&shared), 2);
+}
+
+#[test]
+fn test_dropping_the_lake_drops_tracked_values() {
+    let shared: Arc<()> = Arc::new(());
+    {
+        let mut lake: DynLake = DynLake::new(128);
+        lake.alloc_tracked(vec![shared.clone(), shared.clone()]);
+        assert_eq!(Arc::strong_count(&shared), 3);
+    }
+    assert_eq!(Arc::strong_count(&shared), 1);
+}
+
+#[test]
+fn test_plain_values_are_not_tracked() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    assert_eq!(*lake.alloc_tracked(7u64), 7);
+    assert!(lake.drops().unwrap().is_empty());
+}
+
+#[test]
+fn test_views_refuse_tracked_values() {
+    let shared: Arc<()> = Arc::new(());
+    let mut lake: Lake<256> = Lake::<256>::new();
+    let mut view = lake.split(64).unwrap();
+    assert!(matches!(view.try_alloc_tracked(shared.clone()), Err(LakeError::Untracked)));
+    assert_eq!(Arc::strong_count(&shared), 1);
+    assert_eq!(view.used(), 0);
+}
+
+#[test]
+fn test_parent_reset_under_a_view_with_a_tracked_string() {
+    let mut lake: Lake<256> = Lake::<256>::new();
+    let mut view = lake.split(64).unwrap();
+    assert!(view.try_alloc_tracked(String::from("river")).is_err());
+
+    lake.reset();
+    let _refill = lake.alloc_dyn(256).unwrap();
+    lake.as_mut_slice().fill(0x41);
+    drop(view);
+}
+
+#[test]
+fn test_tracking_in_chained_lakes() {
+    let shared: Arc<()> = Arc::new(());
+    let mut chained: ChainedLake<16> = ChainedLake::<16>::new();
+    for _ in 0..8 {
+        chained.alloc_tracked(shared.clone());
+    }
+    assert!(chained.chunk_count() > 1);
+    assert_eq!(Arc::strong_count(&shared), 9);
+    chained.reset();
+    assert_eq!(Arc::strong_count(&shared), 1);
+}
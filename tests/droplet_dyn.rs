@@ -1,6 +1,7 @@
 use lake::{DropletBase, Lake};
 use lake::droplet_dyn::DropletDyn;
 use lake::lake::droplet::DropletDeserializeExt;
+use lake::lake::LakeError;
 
 #[test]
 fn test_droplet_dyn_process_and_access() {
@@ -79,3 +80,46 @@ fn test_droplet_dyn_invalid_after_reset() {
 
     assert!(!droplet.is_valid());
 }
+
+#[test]
+fn test_droplet_dyn_validate_explains_staleness() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let droplet: DropletDyn<64> = lake.process(|_| vec![1, 2, 3, 4]).unwrap();
+    assert!(droplet.validate().is_ok());
+
+    lake.reset();
+
+    match droplet.validate() {
+        Err(LakeError::StaleGeneration { expected, found }) => {
+            assert_eq!(expected, 0);
+            assert_eq!(found, 1);
+        }
+        other => panic!("expected stale generation, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_droplet_dyn_validate_after_rewind() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let _ = lake.process(|_| vec![0; 8]).unwrap();
+    let droplet: DropletDyn<64> = lake.process(|_| vec![1, 2, 3, 4]).unwrap();
+
+    lake.reset_to(8);
+
+    assert!(matches!(droplet.validate(), Err(LakeError::Rewound { offset: 12, current: 4 })));
+}
+
+#[test]
+fn test_droplet_dyn_try_deserialize() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let droplet: DropletDyn<64> = lake.process(|_| vec![1, 2, 3]).unwrap();
+
+    assert_eq!(*droplet.try_deserialize::<[u8; 3]>().unwrap(), [1, 2, 3]);
+    assert!(matches!(
+        droplet.try_deserialize::<u32>(),
+        Err(LakeError::Overflow { requested: 4, remaining: 3, .. })
+    ));
+
+    let odd: DropletDyn<64> = lake.process(|_| vec![0; 8]).unwrap();
+    assert!(matches!(odd.try_deserialize::<u32>(), Err(LakeError::Misaligned { align: 4, .. })));
+}
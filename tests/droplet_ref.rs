@@ -0,0 +1,76 @@
+use lake::droplet_ref::{DropletDynRef, DropletRef};
+use lake::lake::droplet::DropletDeserializeExt;
+use lake::lake::{LakeAllocatorExt, LakeError};
+use lake::{DropletBase, DynLake, Lake};
+
+#[test]
+fn test_droplet_ref_basic_usage() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let mut droplet: DropletRef<16> = lake.alloc_ref::<16>().unwrap();
+    droplet.copy_from_slice(&[3u8; 16]);
+    assert_eq!(droplet.d_len(), 16);
+    assert_eq!(&*droplet, &[3u8; 16]);
+    drop(droplet);
+    assert_eq!(lake.used(), 16);
+}
+
+#[test]
+fn test_droplet_ref_overflow() {
+    let mut lake: Lake<8> = Lake::<8>::new();
+    assert!(lake.alloc_ref::<16>().is_none());
+    assert!(lake.alloc_dyn_ref(9).is_none());
+    assert!(lake.is_empty());
+}
+
+#[test]
+fn test_droplet_dyn_ref_writes() {
+    let mut lake: DynLake = DynLake::new(64);
+    let mut droplet: DropletDynRef = lake.alloc_dyn_ref(8).unwrap();
+    droplet.d_write(b"ab".as_ptr(), 2);
+    droplet.write_byte(b'c');
+    assert_eq!(droplet.d_offset(), 3);
+    assert_eq!(&droplet[..3], b"abc");
+}
+
+#[test]
+fn test_droplet_ref_deserialize_and_into_mut() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let bytes: &mut [u8; 4] = lake.alloc_ref::<4>().unwrap().into_mut();
+    bytes.copy_from_slice(&7u32.to_ne_bytes());
+
+    let mut view = lake.split(16).unwrap();
+    let droplet: DropletRef<2> = view.alloc_ref::<2>().unwrap();
+    assert!(matches!(droplet.try_deserialize::<u32>(), Err(LakeError::Overflow { requested: 4, .. })));
+    assert_eq!(droplet.deserialize::<u16>().copied(), Some(0));
+}
+
+#[test]
+fn test_droplet_ref_is_send() {
+    fn assert_send<T: Send>(_: &T) {}
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let droplet = lake.alloc_dyn_ref(8).unwrap();
+    assert_send(&droplet);
+}
+
+#[test]
+fn test_ref_scope_hands_out_many_droplets() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let (a, b, c): (DropletRef<8>, DropletDynRef, DropletRef<8>) = {
+        let scope = lake.ref_scope();
+        let mut a: DropletRef<8> = scope.alloc_ref::<8>().unwrap();
+        let mut b: DropletDynRef = scope.alloc_dyn_ref(16).unwrap();
+        let mut c: DropletRef<8> = scope.alloc_ref::<8>().unwrap();
+        assert!(scope.alloc_dyn_ref(33).is_none());
+        a.fill(1);
+        b.fill(2);
+        c.fill(3);
+        (a, b, c)
+    };
+    assert_eq!(&*a, &[1u8; 8]);
+    assert_eq!(&*b, &[2u8; 16]);
+    assert_eq!(&*c, &[3u8; 8]);
+
+    assert_eq!(lake.used(), 32);
+    assert_eq!(lake.as_slice()[7..9], [1, 2]);
+    assert_eq!(lake.as_slice()[23..25], [2, 3]);
+}
@@ -0,0 +1,91 @@
+use lake::droplet::Droplet;
+use lake::droplet_dyn::DropletDyn;
+use lake::lake::{LakeAllocatorExt, LakeMeta, LakeSandboxExt, LakeSnapshot};
+use lake::{DropletBase, DynLake};
+
+fn tenant_lake(capacity: usize) -> DynLake {
+    DynLake::new(capacity)
+}
+
+#[test]
+fn test_dyn_lake_runtime_capacity() {
+    for capacity in [16, 100, 4096] {
+        let lake: DynLake = tenant_lake(capacity);
+        assert_eq!(lake.capacity(), capacity);
+        assert_eq!(lake.remaining(), capacity);
+        assert!(lake.is_empty());
+    }
+}
+
+#[test]
+fn test_dyn_lake_alloc_and_overflow() {
+    let mut lake: DynLake = DynLake::new(32);
+    let mut droplet: Droplet<16, DynLake> = lake.alloc::<16>().unwrap();
+    droplet.copy_from_slice(&[3u8; 16]);
+    assert_eq!(lake.as_slice(), &[3u8; 16]);
+    assert!(lake.alloc::<17>().is_none());
+    assert!(lake.alloc_dyn(17).is_none());
+    let _ = lake.alloc_dyn(16).unwrap();
+    assert!(lake.is_full());
+}
+
+#[test]
+fn test_dyn_lake_process() {
+    let mut lake: DynLake = DynLake::new(64);
+    let droplet: DropletDyn = lake.process(|remaining| vec![9u8; remaining.min(10)]).unwrap();
+    assert_eq!(droplet.d_as_slice(), &[9u8; 10]);
+    assert!(lake.process(|remaining| vec![0u8; remaining + 1]).is_err());
+}
+
+#[test]
+fn test_dyn_lake_snapshot_marks_and_generation() {
+    let mut lake: DynLake = DynLake::new(64);
+    let droplet = lake.alloc::<8>().unwrap();
+    let snap: LakeSnapshot = lake.snapshot();
+    let mark = lake.mark();
+    let _ = lake.alloc::<8>().unwrap();
+    lake.reset_to_mark(mark).unwrap();
+    assert_eq!(lake.used(), 8);
+    let _ = lake.alloc::<8>().unwrap();
+    lake.rewind(snap);
+    assert_eq!(lake.used(), 8);
+    lake.reset();
+    assert!(!droplet.is_valid());
+    assert_eq!(lake.generation(), 1);
+}
+
+#[test]
+fn test_dyn_lake_allocator_ext() {
+    let mut lake: DynLake = DynLake::new(64);
+    let value: &mut u32 = lake.alloc_struct();
+    *value = 7;
+    let slice: &mut [u16] = lake.alloc_slice(4);
+    slice.copy_from_slice(&[1, 2, 3, 4]);
+    assert_eq!(slice, &[1, 2, 3, 4]);
+    assert_eq!(lake.used(), 12);
+}
+
+#[test]
+fn test_dyn_lake_sandbox() {
+    let mut lake: DynLake = DynLake::new(64);
+    let _ = lake.alloc::<8>().unwrap();
+    {
+        let mut sandbox = lake.sandbox();
+        let _ = sandbox.view().alloc::<16>().unwrap();
+    }
+    assert_eq!(lake.used(), 8);
+    assert_eq!(lake.stats().remaining, 56);
+}
+
+#[test]
+fn test_dyn_lake_process_in_place() {
+    let mut lake: DynLake = DynLake::new(16);
+    let droplet = lake
+        .process_in_place(|buf: &mut [u8]| {
+            buf[..4].copy_from_slice(&7u32.to_le_bytes());
+            Ok::<_, std::io::Error>(4)
+        })
+        .unwrap();
+    assert_eq!(droplet.d_as_slice(), &7u32.to_le_bytes());
+    assert_eq!(lake.remaining(), 12);
+}
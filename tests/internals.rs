@@ -46,9 +46,9 @@ mod tests {
 
     #[test]
     fn test_lake_snapshot_equality() {
-        let snap1 = LakeSnapshot { offset: 42 };
-        let snap2 = LakeSnapshot { offset: 42 };
-        let snap3 = LakeSnapshot { offset: 99 };
+        let snap1 = LakeSnapshot { offset: 42, ..LakeSnapshot::default() };
+        let snap2 = LakeSnapshot { offset: 42, ..LakeSnapshot::default() };
+        let snap3 = LakeSnapshot { offset: 99, ..LakeSnapshot::default() };
         assert_eq!(snap1, snap2);
         assert_ne!(snap1, snap3);
     }
@@ -0,0 +1,74 @@
+use lake::journal::JournalGuard;
+use lake::lake::{LakeAllocatorExt, LakeError, LakeSandboxExt};
+use lake::lake_view::LakeView;
+use lake::{DynLake, Lake};
+
+#[test]
+fn test_journal_rollback_restores_patched_header() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    lake.alloc_slice_copy(&[0xAAu8; 8]);
+
+    {
+        let mut sandbox: JournalGuard<'_, Lake<64>> = lake.journaled_sandbox();
+        sandbox.write(2, &[1, 2, 3]).unwrap();
+        sandbox.write(3, &[9, 9]).unwrap();
+        let body = sandbox.view().alloc_slice_copy(b"body");
+        assert_eq!(body, b"body");
+        assert_eq!(sandbox.view().as_slice()[..6], [0xAA, 0xAA, 1, 9, 9, 0xAA]);
+    }
+
+    assert_eq!(lake.get_offset(), 8);
+    assert_eq!(lake.as_slice(), &[0xAA; 8]);
+}
+
+#[test]
+fn test_journal_commit_keeps_patches() {
+    let mut lake: DynLake = DynLake::new(32);
+    lake.alloc_slice_copy(&[0u8; 4]);
+
+    let mut sandbox: JournalGuard<'_, DynLake> = lake.journaled_sandbox();
+    sandbox.region_mut(0, 4).unwrap().copy_from_slice(&7u32.to_le_bytes());
+    sandbox.view().alloc_slice_copy(&[5u8; 4]);
+    let lake: &mut DynLake = sandbox.commit_and_return();
+
+    assert_eq!(lake.as_slice(), &[7, 0, 0, 0, 5, 5, 5, 5]);
+}
+
+#[test]
+fn test_journal_only_saves_committed_bytes() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    lake.alloc_slice_copy(&[1u8; 4]);
+
+    let mut sandbox = lake.journaled_sandbox();
+    sandbox.view().alloc_slice_copy(&[2u8; 8]);
+    sandbox.write(2, &[3; 6]).unwrap();
+    assert_eq!(sandbox.journaled(), 2);
+    sandbox.write(6, &[4; 4]).unwrap();
+    assert_eq!(sandbox.journaled(), 2);
+}
+
+#[test]
+fn test_journal_refuses_unallocated_bytes() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    lake.alloc_slice_copy(&[0u8; 4]);
+
+    let mut sandbox = lake.journaled_sandbox();
+    assert!(matches!(
+        sandbox.write(2, &[0; 4]),
+        Err(LakeError::OutOfBounds { offset: 2, len: 4, used: 4 })
+    ));
+    assert!(sandbox.region_mut(usize::MAX, 2).is_err());
+    assert_eq!(sandbox.journaled(), 0);
+}
+
+#[test]
+fn test_journal_on_view() {
+    let mut parent: Lake<64> = Lake::<64>::new();
+    let mut view: LakeView<64> = parent.split(32).unwrap();
+    view.alloc_slice_copy(b"head");
+    {
+        let mut sandbox = view.journaled_sandbox();
+        sandbox.write(0, b"HE").unwrap();
+    }
+    assert_eq!(view.as_slice(), b"head");
+}
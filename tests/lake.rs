@@ -1,6 +1,6 @@
 use lake::droplet::Droplet;
-use lake::Lake;
-use lake::lake::LakeSnapshot;
+use lake::{DropletBase, Lake};
+use lake::lake::{LakeError, LakeSnapshot};
 
 #[test]
 fn test_new_lake_is_empty() {
@@ -42,9 +42,9 @@ fn test_reset_behavior() {
 fn test_mark_and_reset_to_mark() {
     let mut lake: Lake<64> = Lake::<64>::new();
     let _ = lake.alloc::<10>().unwrap();
-    lake.mark();
+    let mark = lake.mark();
     let _ = lake.alloc::<20>().unwrap();
-    lake.reset_to_mark();
+    lake.reset_to_mark(mark).unwrap();
     assert_eq!(lake.used(), 10);
 }
 
@@ -52,10 +52,10 @@ fn test_mark_and_reset_to_mark() {
 fn test_move_mark() {
     let mut lake: Lake<64> = Lake::<64>::new();
     let _ = lake.alloc::<8>().unwrap();
-    lake.mark(); // offset = 8
+    let mark = lake.mark(); // offset = 8
     let _ = lake.alloc::<16>().unwrap(); // offset = 24
     lake.move_mark();
-    lake.reset_to_mark(); // should reset to 24
+    lake.reset_to_mark(mark).unwrap(); // should reset to 24
     assert_eq!(lake.used(), 24);
 }
 
@@ -94,3 +94,68 @@ fn test_reset_to_bytes() {
     lake.reset_to(16);
     assert_eq!(lake.used(), 16);
 }
+
+#[test]
+fn test_try_process_maps_closure_error() {
+    let mut lake: Lake<32> = Lake::<32>::new();
+    let _ = lake.alloc::<4>().unwrap();
+
+    let result = lake.try_process(|_| Err::<Vec<u8>, _>("upstream closed"));
+    match result {
+        Err(err @ LakeError::Process(_)) => {
+            assert_eq!(err.to_string(), "process failed: upstream closed");
+            assert!(std::error::Error::source(&err).is_some());
+        }
+        _ => panic!("expected process error"),
+    }
+    // Nothing was written.
+    assert_eq!(lake.used(), 4);
+
+    let droplet = lake.try_process(|_| Ok::<_, std::io::Error>(vec![7; 4])).unwrap();
+    assert_eq!(droplet.d_len(), 4);
+    assert_eq!(lake.used(), 8);
+}
+
+#[test]
+fn test_lake_error_display() {
+    let err: LakeError = LakeError::Overflow { requested: 16, align: 8, remaining: 4, capacity: 32 };
+    let msg: String = err.to_string();
+    assert!(msg.contains("16"));
+    assert!(msg.contains("4"));
+    assert!(msg.contains("32"));
+
+    let err: LakeError = LakeError::StaleGeneration { expected: 1, found: 3 };
+    assert!(err.to_string().contains("generation"));
+}
+
+#[test]
+fn test_process_in_place_commits_written_len() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let _ = lake.alloc::<4>().unwrap();
+
+    let droplet = lake
+        .process_in_place(|buf: &mut [u8]| {
+            assert_eq!(buf.len(), 60);
+            buf[..5].copy_from_slice(b"hello");
+            Ok::<_, std::io::Error>(5)
+        })
+        .unwrap();
+
+    assert_eq!(droplet.d_as_slice(), b"hello");
+    assert_eq!(lake.used(), 9);
+}
+
+#[test]
+fn test_process_in_place_rolls_back_on_error() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let result = lake.process_in_place(|buf: &mut [u8]| {
+        buf[..3].copy_from_slice(b"abc");
+        Err::<usize, _>("encoder gave up")
+    });
+    assert!(matches!(result, Err(LakeError::Process(_))));
+    assert!(lake.is_empty());
+
+    let result = lake.process_in_place(|buf: &mut [u8]| Ok::<_, std::io::Error>(buf.len() + 1));
+    assert!(matches!(result, Err(LakeError::Overflow { requested: 65, .. })));
+    assert!(lake.is_empty());
+}
@@ -0,0 +1,103 @@
+use lake::lake_view::LakeView;
+use lake::lake::{LakeAlloc, LakeError};
+use lake::{ChainedLake, DropletBase, DynLake, Lake};
+
+/// Run the same script against any lake: it must behave identically everywhere.
+fn fill_and_rewind(lake: &mut impl LakeAlloc) {
+    assert!(lake.is_empty());
+    assert_eq!(lake.capacity(), 32);
+
+    let mut head = lake.alloc::<8>().unwrap();
+    head.fill(1);
+    let snapshot = lake.snapshot();
+    let droplet = lake.alloc_dyn(16).unwrap();
+    assert_eq!(droplet.d_len(), 16);
+    assert_eq!(lake.used(), 24);
+    assert_eq!(lake.peek::<8>().map(|next| next.len()), Some(8));
+    assert!(lake.peek::<9>().is_none());
+    assert!(lake.alloc::<9>().is_none());
+    assert!(lake.alloc_dyn(usize::MAX).is_none());
+
+    lake.rewind(snapshot);
+    assert_eq!(lake.used(), 8);
+    assert_eq!(lake.as_slice(), &[1; 8]);
+
+    let _ = lake.alloc_dyn(24).unwrap();
+    assert!(lake.is_full());
+    lake.reset_to(4);
+    assert_eq!(lake.remaining(), 4);
+
+    lake.reset();
+    assert!(lake.is_empty());
+    assert_eq!(lake.generation(), 1);
+}
+
+#[test]
+fn test_lake_alloc_same_behaviour_everywhere() {
+    fill_and_rewind(&mut Lake::<32>::new());
+    fill_and_rewind(&mut DynLake::new(32));
+
+    let mut parent: Lake<128> = Lake::<128>::new();
+    let _ = parent.alloc::<64>().unwrap();
+    let mut view: LakeView<128> = parent.split(32).unwrap();
+    fill_and_rewind(&mut view);
+}
+
+fn rewind_into_droplet(lake: &mut impl LakeAlloc) {
+    let _ = lake.alloc::<4>().unwrap();
+    let fixed = lake.alloc::<4>().unwrap();
+    assert!(fixed.is_valid());
+
+    lake.reset_to(2);
+    assert!(matches!(fixed.validate(), Err(LakeError::Rewound { offset: 8, current: 6 })));
+}
+
+#[test]
+fn test_droplet_offsets_are_consistent() {
+    rewind_into_droplet(&mut Lake::<32>::new());
+    rewind_into_droplet(&mut DynLake::new(32));
+
+    let mut parent: Lake<64> = Lake::<64>::new();
+    let mut view: LakeView<64> = parent.split(32).unwrap();
+    rewind_into_droplet(&mut view);
+
+    rewind_into_droplet(&mut ChainedLake::<32>::new());
+}
+
+/// Generic code sees a chained lake grow where any other lake would run dry.
+fn fill_past_first_chunk(lake: &mut impl LakeAlloc) -> usize {
+    let mut head = lake.alloc::<24>().unwrap();
+    head.fill(7);
+    assert_eq!(lake.peek::<8>(), Some(&[0u8; 8]));
+    let _ = lake.alloc_dyn(16).unwrap();
+    lake.used()
+}
+
+#[test]
+fn test_chained_lake_through_lake_alloc() {
+    let mut lake: ChainedLake<32> = ChainedLake::<32>::new();
+    assert_eq!(fill_past_first_chunk(&mut lake), 48);
+    assert_eq!(lake.chunk_count(), 2);
+    // Only the current chunk is contiguous.
+    assert_eq!(LakeAlloc::as_slice(&lake).len(), 16);
+
+    lake.reset_to(24);
+    assert_eq!(LakeAlloc::as_slice(&lake), &[7; 24]);
+    assert!(lake.peek::<9>().is_none());
+
+    LakeAlloc::reset(&mut lake);
+    assert!(LakeAlloc::is_empty(&lake));
+}
+
+#[test]
+fn test_write_cursor_is_independent_of_lake_offset() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let _ = lake.alloc::<40>().unwrap();
+
+    let mut droplet = lake.alloc_dyn(8).unwrap();
+    assert_eq!(droplet.d_remaining(), 8);
+    droplet.d_write(b"abcd".as_ptr(), 4);
+    assert_eq!(droplet.d_remaining(), 4);
+    assert_eq!(&droplet.d_as_slice()[..4], b"abcd");
+    assert!(droplet.is_valid());
+}
@@ -1,5 +1,6 @@
 use lake::{
-    lake::LakeAllocatorExt,
+    lake::{LakeAllocatorExt, LakeError},
+    lake_view::LakeView,
     Lake,
 };
 
@@ -27,3 +28,86 @@ fn test_alloc_slice() {
     slice.copy_from_slice(&[1, 2, 3]);
     assert_eq!(slice, &[1, 2, 3]);
 }
+
+#[test]
+fn test_try_alloc_struct_overflow() {
+    let mut lake: Lake<16> = Lake::<16>::new();
+    let _ = lake.alloc::<3>().unwrap();
+    let result: Result<&mut [u64; 2], LakeError> = lake.try_alloc_struct();
+    match result {
+        Err(LakeError::Overflow { requested, align, remaining, capacity }) => {
+            assert_eq!(requested, 16);
+            assert_eq!(align, 8);
+            assert_eq!(remaining, 13);
+            assert_eq!(capacity, 16);
+        }
+        _ => panic!("expected overflow"),
+    }
+    // A failed allocation leaves the lake untouched.
+    assert_eq!(lake.used(), 3);
+    assert!(lake.try_alloc_struct::<u64>().is_ok());
+}
+
+#[test]
+fn test_try_alloc_slice_checked_size() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let result = lake.try_alloc_slice::<u32>(usize::MAX / 2);
+    assert!(matches!(result, Err(LakeError::SizeOverflow { size: 4, .. })));
+    let result = lake.try_alloc_slice::<u32>(33);
+    assert!(matches!(result, Err(LakeError::Overflow { requested: 132, .. })));
+    assert_eq!(lake.try_alloc_slice::<u32>(32).unwrap().len(), 32);
+}
+
+#[test]
+fn test_try_alloc_in_view() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let mut view: LakeView<128> = lake.split(16).unwrap();
+    assert!(view.try_alloc_slice::<u8>(16).is_ok());
+    assert!(matches!(view.try_alloc_struct::<u8>(), Err(LakeError::Overflow { remaining: 0, .. })));
+}
+
+#[test]
+#[should_panic(expected = "Lake overflow")]
+fn test_alloc_struct_panics_on_overflow() {
+    let mut lake: Lake<4> = Lake::<4>::new();
+    let _ = lake.alloc_struct::<u64>();
+}
+
+#[test]
+fn test_alloc_value_and_with() {
+    #[derive(Debug, PartialEq)]
+    enum State {
+        Idle,
+        Busy(u32),
+    }
+
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let flag: &mut bool = lake.alloc_value(true);
+    assert!(*flag);
+    let state: &mut State = lake.alloc_with(|| State::Busy(7));
+    assert_eq!(*state, State::Busy(7));
+    *state = State::Idle;
+    assert_eq!(*state, State::Idle);
+    assert!(matches!(lake.try_alloc_with(|| [0u64; 32]), Err(LakeError::Overflow { .. })));
+}
+
+#[test]
+fn test_alloc_slice_copy_fill_and_str() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let copied: &mut [u16] = lake.alloc_slice_copy(&[1, 2, 3]);
+    assert_eq!(copied, &[1, 2, 3]);
+    let squares: &mut [u32] = lake.alloc_slice_fill_with(4, |i| (i * i) as u32);
+    assert_eq!(squares, &[0, 1, 4, 9]);
+    let name: &mut str = lake.alloc_str("still waters");
+    name.make_ascii_uppercase();
+    assert_eq!(name, "STILL WATERS");
+}
+
+#[test]
+fn test_alloc_value_in_view() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let mut view: LakeView<128> = lake.split(32).unwrap();
+    let value: &mut Option<u64> = view.alloc_value(Some(5));
+    assert_eq!(*value, Some(5));
+    assert_eq!(view.alloc_str("tributary"), "tributary");
+}
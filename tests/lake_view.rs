@@ -1,7 +1,7 @@
 use lake::droplet::Droplet;
 use lake::droplet_dyn::DropletDyn;
 use lake::lake_view::LakeView;
-use lake::Lake;
+use lake::{DropletBase, Lake};
 use lake::lake::{LakeAllocatorExt, LakeError};
 
 #[test]
@@ -43,9 +43,9 @@ fn test_lake_view_mark_reset() {
     let mut bind: [u8; 32] = [0u8; 32];
     let mut view: LakeView<32> = LakeView::<32>::new(&mut bind);
     let _ = view.alloc::<8>().unwrap();
-    view.mark();
+    let mark = view.mark();
     let _ = view.alloc::<8>().unwrap();
-    view.reset_to_mark();
+    view.reset_to_mark(mark).unwrap();
     assert_eq!(view.used(), 8);
 }
 
@@ -54,11 +54,11 @@ fn test_lake_view_move_mark() {
     let mut bind: [u8; 32] = [0u8; 32];
     let mut view: LakeView<32> = LakeView::<32>::new(&mut bind);
     let _ = view.alloc::<4>().unwrap(); // offset = 4
-    view.mark(); // push 4
+    let mark = view.mark(); // push 4
     let _ = view.alloc::<6>().unwrap(); // offset = 10
     view.move_mark(); // mark should now be 10
     let _ = view.alloc::<2>().unwrap(); // offset = 12
-    view.reset_to_mark(); // back to 10
+    view.reset_to_mark(mark).unwrap(); // back to 10
     assert_eq!(view.used(), 10);
 }
 
@@ -89,7 +89,7 @@ fn test_lake_view_process_overflow() {
     let mut bind: [u8; 16] = [0u8; 16];
     let mut view: LakeView<16> = LakeView::<16>::new(&mut bind);
     let result: Result<DropletDyn<16>, LakeError> = view.process(|_| vec![1u8; 32]); // too much
-    assert!(matches!(result, Err(LakeError::Overflow)));
+    assert!(matches!(result, Err(LakeError::Overflow { .. })));
 }
 
 #[test]
@@ -116,3 +116,68 @@ fn test_lake_view_alloc_slice() {
     slice.copy_from_slice(&[1, 2, 3, 4]);
     assert_eq!(slice, &[1, 2, 3, 4]);
 }
+
+#[test]
+fn test_view_process_in_place_sees_only_view_water() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let mut view: LakeView<128> = lake.split(32).unwrap();
+    let _ = view.alloc::<8>().unwrap();
+    let droplet = view
+        .process_in_place(|buf: &mut [u8]| {
+            assert_eq!(buf.len(), 24);
+            buf[..2].copy_from_slice(b"ok");
+            Ok::<_, std::io::Error>(2)
+        })
+        .unwrap();
+    assert_eq!(droplet.d_as_slice(), b"ok");
+    assert_eq!(view.used(), 10);
+}
+
+#[test]
+fn test_view_process_is_bounded_by_view_capacity() {
+    let mut lake: Lake<256> = Lake::<256>::new();
+    let mut left: LakeView<256> = lake.split(16).unwrap();
+    let mut right: LakeView<256> = lake.split(16).unwrap();
+    right.alloc_dyn(16).unwrap().d_as_mut_slice().fill(0xAA);
+
+    let result = left.process(|remaining| {
+        assert_eq!(remaining, 16);
+        vec![0xFF; 32]
+    });
+    assert!(matches!(
+        result,
+        Err(LakeError::Overflow { requested: 32, remaining: 16, capacity: 16, .. })
+    ));
+    assert!(left.is_empty());
+
+    let droplet: DropletDyn<256> = left.process(|remaining| vec![1; remaining]).unwrap();
+    assert_eq!(droplet.d_len(), 16);
+    assert!(left.is_full());
+    assert!(left.process(|_| vec![1]).is_err());
+    assert!(lake.as_slice()[16..32].iter().all(|&b| b == 0xAA));
+}
+
+#[test]
+fn test_nested_views_stay_inside_their_bounds() {
+    let mut lake: Lake<1024> = Lake::<1024>::new();
+    let mut outer: LakeView<1024> = lake.split(64).unwrap();
+    let _ = outer.alloc::<40>().unwrap();
+    assert!(outer.split(25).is_none());
+
+    let mut inner: LakeView<1024> = outer.split(24).unwrap();
+    assert!(inner.peek::<24>().is_some());
+    assert!(inner.peek::<25>().is_none());
+    assert!(inner.alloc_dyn(25).is_none());
+
+    let mut leaf: LakeView<1024> = inner.split(8).unwrap();
+    assert!(leaf.alloc::<9>().is_none());
+    assert!(leaf.alloc_dyn(usize::MAX).is_none());
+    assert_eq!(leaf.process(|remaining| vec![7; remaining]).unwrap().d_len(), 8);
+    assert!(leaf.peek::<1>().is_none());
+
+    assert_eq!(inner.remaining(), 16);
+    let rest: DropletDyn<1024> = inner.process(|remaining| vec![3; remaining]).unwrap();
+    assert_eq!(rest.d_len(), 16);
+    assert!(inner.split(1).is_none());
+    assert!(outer.is_full());
+}
@@ -0,0 +1,115 @@
+use lake::lake::LakeError;
+use lake::lake_view::LakeView;
+use lake::mark::MarkToken;
+use lake::{DynLake, Lake};
+
+/// A recursive-descent "parser" stage that marks, parses and always backtracks.
+fn parse_and_backtrack(lake: &mut Lake<256>, depth: usize) {
+    let mark: MarkToken = lake.mark_named("stage");
+    let _ = lake.alloc::<8>().unwrap();
+    if depth > 0 {
+        parse_and_backtrack(lake, depth - 1);
+    }
+    lake.reset_to_mark(mark).unwrap();
+}
+
+#[test]
+fn test_nested_marks_rewind_to_their_own_level() {
+    let mut lake: Lake<256> = Lake::<256>::new();
+    let outer: MarkToken = lake.mark();
+    let _ = lake.alloc::<16>().unwrap();
+    parse_and_backtrack(&mut lake, 4);
+    assert_eq!(lake.used(), 16);
+    assert_eq!(lake.marks(), 1);
+
+    lake.reset_to_mark(outer).unwrap();
+    assert_eq!(lake.used(), 0);
+    assert_eq!(lake.marks(), 0);
+}
+
+#[test]
+fn test_reset_to_outer_mark_pops_inner_ones() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let outer: MarkToken = lake.mark();
+    let _ = lake.alloc::<8>().unwrap();
+    let inner: MarkToken = lake.mark();
+    let _ = lake.alloc::<8>().unwrap();
+    assert_eq!((outer.depth, inner.depth), (0, 1));
+
+    lake.reset_to_mark(outer).unwrap();
+    assert_eq!(lake.used(), 0);
+    assert!(matches!(
+        lake.reset_to_mark(inner),
+        Err(LakeError::InvalidMark { depth: 1, marks: 0 })
+    ));
+}
+
+#[test]
+fn test_popped_mark_is_not_confused_with_a_newer_one() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let old: MarkToken = lake.mark();
+    let _ = lake.alloc::<8>().unwrap();
+    lake.reset_to_mark(old).unwrap();
+
+    let _ = lake.alloc::<4>().unwrap();
+    let new: MarkToken = lake.mark();
+    assert_eq!(old.depth, new.depth);
+    assert!(matches!(lake.reset_to_mark(old), Err(LakeError::InvalidMark { .. })));
+    assert_eq!(lake.marks(), 1);
+    lake.reset_to_mark(new).unwrap();
+    assert_eq!(lake.used(), 4);
+}
+
+#[test]
+fn test_mark_from_another_lake_is_refused() {
+    let mut a: Lake<64> = Lake::<64>::new();
+    let mut b: Lake<64> = Lake::<64>::new();
+    let _ = a.alloc::<8>().unwrap();
+    let token: MarkToken = a.mark();
+    let _ = b.alloc::<16>().unwrap();
+    let _own: MarkToken = b.mark();
+    let _ = b.alloc::<16>().unwrap();
+
+    // Same depth, same generation, but not `b`'s mark.
+    assert!(matches!(b.reset_to_mark(token), Err(LakeError::InvalidMark { depth: 0, marks: 1 })));
+    assert_eq!(b.used(), 32);
+    assert_eq!(b.marks(), 1);
+    a.reset_to_mark(token).unwrap();
+}
+
+#[test]
+fn test_mark_from_older_generation_is_stale() {
+    let mut lake: DynLake = DynLake::new(64);
+    let mark: MarkToken = lake.mark();
+    lake.reset();
+    let _ = lake.alloc::<8>().unwrap();
+    assert!(matches!(
+        lake.reset_to_mark(mark),
+        Err(LakeError::StaleGeneration { expected: 0, found: 1 })
+    ));
+    assert_eq!(lake.used(), 8);
+}
+
+#[test]
+fn test_mark_above_water_level_is_refused() {
+    let mut bind: [u8; 64] = [0u8; 64];
+    let mut view: LakeView<64> = LakeView::<64>::new(&mut bind);
+    let _ = view.alloc::<16>().unwrap();
+    let mark: MarkToken = view.mark();
+    view.reset_to(16);
+    assert!(matches!(
+        view.reset_to_mark(mark),
+        Err(LakeError::InvalidSnapshot { offset: 16, current: 0 })
+    ));
+    assert_eq!(view.marks(), 1);
+}
+
+#[test]
+fn test_named_mark_shows_up_in_debug() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let mark: MarkToken = lake.mark_named("json-object");
+    assert_eq!(mark.name, Some("json-object"));
+    assert!(format!("{mark:?}").contains("json-object"));
+    lake.move_mark();
+    lake.reset_to_mark(mark).unwrap();
+}
@@ -0,0 +1,97 @@
+use lake::lake::{LakeAllocatorExt, LakeMeta};
+use lake::lake_view::LakeView;
+use lake::sandbox::{MultiSandbox, SandboxSlot};
+use lake::{ChainedLake, DynLake, Lake};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[test]
+fn test_multi_sandbox_rolls_back_every_lake() {
+    let mut header: Lake<64> = Lake::<64>::new();
+    let mut parent: Lake<128> = Lake::<128>::new();
+    let mut body: LakeView<128> = parent.split(64).unwrap();
+    let mut trailer: DynLake = DynLake::new(32);
+    let _ = header.alloc::<4>().unwrap();
+    let _ = trailer.alloc_dyn(2).unwrap();
+
+    {
+        let mut sandbox: MultiSandbox<'_> = MultiSandbox::new();
+        let h: SandboxSlot<Lake<64>> = sandbox.add(&mut header);
+        let b: SandboxSlot<LakeView<128>> = sandbox.add(&mut body);
+        let t: SandboxSlot<DynLake> = sandbox.add(&mut trailer);
+        let _ = sandbox.get(h).alloc::<8>().unwrap();
+        let _ = sandbox.get(b).alloc::<32>().unwrap();
+        let _ = sandbox.get(t).alloc_dyn(16).unwrap();
+        assert_eq!(sandbox.get(b).get_offset(), 32);
+        assert_eq!(sandbox.base_offset(t.index()), 2);
+    }
+
+    assert_eq!(header.get_offset(), 4);
+    assert_eq!(body.get_offset(), 0);
+    assert_eq!(trailer.offset(), 2);
+}
+
+#[test]
+fn test_multi_sandbox_commits_every_lake() {
+    let mut header: Lake<64> = Lake::<64>::new();
+    let mut body: ChainedLake<32> = ChainedLake::<32>::new();
+
+    let mut sandbox: MultiSandbox<'_> = MultiSandbox::new();
+    let h = sandbox.add(&mut header);
+    let b = sandbox.add(&mut body);
+    sandbox.get(h).alloc_str("GET");
+    sandbox.get(b).alloc_slice_copy(&[1u8; 10]);
+    sandbox.commit();
+
+    assert_eq!(header.get_offset(), 3);
+    assert_eq!(body.offset(), 10);
+}
+
+#[test]
+fn test_multi_sandbox_from_trait_objects() {
+    let mut a: Lake<32> = Lake::<32>::new();
+    let mut b: DynLake = DynLake::new(32);
+    let _ = a.alloc::<8>().unwrap();
+
+    {
+        let mut sandbox: MultiSandbox<'_> = MultiSandbox::from_lakes([
+            &mut a as &mut dyn LakeMeta,
+            &mut b as &mut dyn LakeMeta,
+        ]);
+        assert_eq!(sandbox.len(), 2);
+        *sandbox.lake(0).offset_mut() = 20;
+        *sandbox.lake(1).offset_mut() = 12;
+        sandbox.rollback();
+    }
+
+    assert_eq!(a.get_offset(), 8);
+    assert_eq!(b.offset(), 0);
+}
+
+#[test]
+fn test_multi_sandbox_rolls_back_on_panic() {
+    let mut a: Lake<32> = Lake::<32>::new();
+    let mut b: Lake<32> = Lake::<32>::new();
+    let caught = catch_unwind(AssertUnwindSafe(|| {
+        let mut sandbox: MultiSandbox<'_> = MultiSandbox::new();
+        let first = sandbox.add(&mut a);
+        let second = sandbox.add(&mut b);
+        let _ = sandbox.get(first).alloc::<16>().unwrap();
+        let _ = sandbox.get(second).alloc::<16>().unwrap();
+        panic!("encoder failed halfway");
+    }));
+    assert!(caught.is_err());
+    assert_eq!(a.get_offset(), 0);
+    assert_eq!(b.get_offset(), 0);
+}
+
+#[test]
+#[should_panic(expected = "another MultiSandbox")]
+fn test_slot_from_another_sandbox_is_refused() {
+    let mut a: Lake<32> = Lake::<32>::new();
+    let mut b: DynLake = DynLake::new(32);
+    let mut first: MultiSandbox<'_> = MultiSandbox::new();
+    let mut second: MultiSandbox<'_> = MultiSandbox::new();
+    let slot = first.add(&mut a);
+    second.add(&mut b);
+    let _ = second.get(slot);
+}
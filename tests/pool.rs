@@ -0,0 +1,115 @@
+use lake::lake::LakeMeta;
+use lake::{Lake, LakePool, LakePoolStats};
+use std::sync::{mpsc, Arc};
+
+#[test]
+fn test_pool_creates_then_reuses() {
+    let pool: LakePool<256> = LakePool::<256>::new(4);
+    let lake: Lake<256> = pool.acquire();
+    assert_eq!(pool.stats().in_use, 1);
+    pool.release(lake);
+
+    let again: Lake<256> = pool.acquire();
+    let stats: LakePoolStats = pool.stats();
+    assert_eq!(stats.created, 1);
+    assert_eq!(stats.reused, 1);
+    assert_eq!(stats.idle, 0);
+    pool.release(again);
+}
+
+#[test]
+fn test_pool_resets_returned_lakes() {
+    let pool: LakePool<64> = LakePool::<64>::new(1);
+    let mut lake: Lake<64> = pool.acquire();
+    let _ = lake.alloc::<32>().unwrap();
+    let _ = lake.mark();
+    pool.release(lake);
+
+    let lake: Lake<64> = pool.acquire();
+    assert!(lake.is_empty());
+    assert_eq!(lake.marks(), 0);
+    assert_eq!(lake.generation(), 1);
+}
+
+#[test]
+fn test_pool_switches_off_returned_lakes_settings() {
+    let pool: LakePool<64> = LakePool::<64>::new(1);
+    let mut lake: Lake<64> = pool.acquire();
+    lake.set_secure(true);
+    lake.set_zeroing(true);
+    lake.alloc::<16>().unwrap().fill(0xAB);
+    pool.release(lake);
+
+    let mut lake: Lake<64> = pool.acquire();
+    assert!(!lake.is_secure());
+    assert!(!lake.get_zeroing());
+    assert_eq!(lake.peek::<16>(), Some(&[0u8; 16]));
+}
+
+#[test]
+fn test_pool_reports_its_water_as_lake_stats() {
+    let pool: LakePool<128> = LakePool::<128>::with_idle(4, 2);
+    let lake: Lake<128> = pool.acquire();
+    let stats: LakePoolStats = pool.stats();
+    assert_eq!(stats.lakes.capacity, 256);
+    assert_eq!(stats.lakes.used, 128);
+    assert_eq!(stats.lakes.remaining, 128);
+
+    pool.release(lake);
+    let stats: LakePoolStats = pool.stats();
+    assert_eq!(stats.lakes.used, 0);
+    assert_eq!(stats.lakes.remaining, 256);
+    assert_eq!(stats.lakes.generation, 1);
+}
+
+#[test]
+fn test_pool_caps_retained_lakes() {
+    let pool: LakePool<64> = LakePool::<64>::with_idle(2, 5);
+    assert_eq!(pool.stats().idle, 2);
+
+    let lakes: Vec<Lake<64>> = (0..4).map(|_| pool.acquire()).collect();
+    for lake in lakes {
+        pool.release(lake);
+    }
+
+    let stats: LakePoolStats = pool.stats();
+    assert_eq!(stats.idle, 2);
+    assert_eq!(stats.in_use, 0);
+    assert_eq!(stats.created, 4);
+    assert_eq!(stats.discarded, 2);
+    assert_eq!(stats.recycled, 2);
+
+    pool.clear();
+    assert_eq!(pool.stats().idle, 0);
+}
+
+#[test]
+fn test_pool_cross_thread_handoff() {
+    let pool: Arc<LakePool<1024>> = Arc::new(LakePool::<1024>::new(8));
+    let (tx, rx) = mpsc::channel::<Lake<1024>>();
+
+    let worker = {
+        let pool = pool.clone();
+        std::thread::spawn(move || {
+            let mut total: usize = 0;
+            for lake in rx {
+                total += lake.as_slice().iter().map(|&b| b as usize).sum::<usize>();
+                pool.release(lake);
+            }
+            total
+        })
+    };
+
+    for i in 0..16u8 {
+        let mut lake: Lake<1024> = pool.acquire();
+        let mut droplet = lake.alloc::<4>().unwrap();
+        droplet.fill(i);
+        tx.send(lake).unwrap();
+    }
+    drop(tx);
+
+    assert_eq!(worker.join().unwrap(), (0..16).map(|i| i * 4).sum::<usize>());
+    let stats: LakePoolStats = pool.stats();
+    assert_eq!(stats.in_use, 0);
+    assert!(stats.created <= 16);
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "sanitizer")]
+
+use lake::lake::LakeAllocatorExt;
+use lake::sanitizer::{CANARY, POISON, RED_ZONE};
+use lake::{DropletBase, DynLake, Lake};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn test_red_zones_separate_allocations() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    lake.set_sanitizer(true);
+    let first = lake.alloc::<8>().unwrap();
+    let second = lake.alloc_dyn(4).unwrap();
+
+    assert_eq!(second.d_as_ptr() as usize - first.d_as_ptr() as usize, 8 + RED_ZONE);
+    assert_eq!(&lake.as_slice()[8..8 + RED_ZONE], &[CANARY; RED_ZONE]);
+    assert!(first.is_valid());
+}
+
+#[test]
+#[should_panic(expected = "allocation 0..4 (4 bytes) was overrun")]
+fn test_overrun_is_caught_on_droplet_access() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    lake.set_sanitizer(true);
+    let mut droplet = lake.alloc::<4>().unwrap();
+    droplet.write_num_str_fixed(1234, 4);
+    // Slip past the droplet's own bounds, like a buggy encoder would.
+    unsafe { *droplet.d_as_mut_ptr().add(4) = b'!' };
+    let _ = droplet.d_as_slice();
+}
+
+#[test]
+#[should_panic(expected = "was overrun")]
+fn test_overrun_is_caught_on_rewind() {
+    let mut lake: DynLake = DynLake::new(64);
+    lake.set_sanitizer(true);
+    let snapshot = lake.snapshot();
+    let slot: &mut [u8] = lake.alloc_slice::<u8>(8);
+    unsafe { *slot.as_mut_ptr().add(9) = 0 };
+    lake.rewind(snapshot);
+}
+
+#[test]
+fn test_rewound_water_is_poisoned() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    lake.set_sanitizer(true);
+    let _ = lake.alloc::<4>().unwrap();
+    let token = lake.mark();
+    lake.alloc_slice_copy(&[7u8; 8]);
+
+    lake.reset_to_mark(token).unwrap();
+    assert_eq!(lake.peek::<8>(), Some(&[POISON; 8]));
+    assert_eq!(lake.get_offset(), 4 + RED_ZONE);
+}
+
+#[test]
+#[should_panic(expected = "Droplet overflow")]
+fn test_write_byte_is_bounds_checked() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let mut droplet = lake.alloc::<1>().unwrap();
+    droplet.write_byte(b'a');
+    droplet.write_byte(b'b');
+}
+
+#[test]
+fn test_red_zone_is_clipped_at_the_end_of_the_lake() {
+    let mut lake: Lake<32> = Lake::<32>::new();
+    lake.set_sanitizer(true);
+    let droplet = lake.alloc::<24>().unwrap();
+    assert!(lake.is_full());
+    assert!(droplet.is_valid());
+    lake.reset();
+    assert!(lake.is_sanitized());
+}
+
+#[test]
+fn test_tracked_values_keep_their_place_behind_red_zones() {
+    static RUNG: AtomicUsize = AtomicUsize::new(0);
+    struct Bell;
+    impl Drop for Bell {
+        fn drop(&mut self) {
+            RUNG.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut lake: Lake<64> = Lake::<64>::new();
+    lake.set_sanitizer(true);
+    lake.alloc_tracked(Bell);
+    let after = lake.snapshot();
+    lake.alloc_slice_copy(&[1u8; 4]);
+
+    // The bell lives at offset 0, not behind its red zone: rewinding past it must leave it alone.
+    lake.rewind(after);
+    assert_eq!(RUNG.load(Ordering::Relaxed), 0);
+    lake.reset();
+    assert_eq!(RUNG.load(Ordering::Relaxed), 1);
+}
@@ -0,0 +1,61 @@
+use lake::scoped::ScopedDroplet;
+use lake::{ChainedLake, DropletBase, DynLake, Lake};
+
+#[test]
+fn test_scoped_droplet_rewinds_when_top() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let _keep = lake.alloc::<8>().unwrap();
+    {
+        let mut scoped = lake.alloc_scoped::<16>().unwrap();
+        scoped.copy_from_slice(&[7u8; 16]);
+        assert!(scoped.is_top());
+    }
+    assert_eq!(lake.used(), 8);
+}
+
+#[test]
+fn test_scoped_droplet_in_hot_loop_reuses_memory() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    for i in 0..1000 {
+        let mut scratch = lake.alloc_dyn_scoped(48).unwrap();
+        scratch.d_as_mut_slice()[0] = i as u8;
+    }
+    assert!(lake.is_empty());
+}
+
+#[test]
+fn test_scoped_droplet_into_inner_keeps_allocation() {
+    let mut lake: DynLake = DynLake::new(64);
+    let droplet = lake.alloc_scoped::<8>().unwrap().into_inner();
+    assert!(droplet.is_valid());
+    assert_eq!(lake.used(), 8);
+}
+
+#[test]
+fn test_scoped_droplet_across_chunks_and_views() {
+    let mut lake: ChainedLake<16> = ChainedLake::<16>::new();
+    let _base = lake.alloc_dyn(12).unwrap();
+    drop(lake.alloc_dyn_scoped(12).unwrap());
+    assert_eq!(lake.chunk_count(), 2);
+    assert_eq!(lake.used(), 12);
+
+    let mut base: Lake<64> = Lake::<64>::new();
+    let mut view = base.split(32).unwrap();
+    drop(view.alloc_scoped::<16>().unwrap());
+    assert_eq!(view.used(), 0);
+}
+
+#[test]
+fn test_scoped_droplet_borrows_the_lake() {
+    fn fill<'lake>(lake: &'lake mut Lake<64>) -> ScopedDroplet<'lake, lake::droplet_dyn::DropletDyn<64>> {
+        let mut scratch = lake.alloc_dyn_scoped(16).unwrap();
+        scratch.d_as_mut_slice().fill(9);
+        scratch
+    }
+
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let scratch = fill(&mut lake);
+    assert_eq!(scratch.d_as_slice(), &[9u8; 16]);
+    drop(scratch);
+    assert!(lake.is_empty());
+}
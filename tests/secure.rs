@@ -0,0 +1,118 @@
+use lake::lake::{LakeAllocatorExt, LakeError, LakeSandboxExt};
+use lake::{DropletBase, DynLake, Lake};
+
+#[test]
+fn test_secure_rewind_wipes_released_bytes() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    lake.set_secure(true);
+    lake.alloc_slice_copy(b"user");
+    let snapshot = lake.snapshot();
+    lake.alloc_slice_copy(&[0xEEu8; 32]);
+
+    lake.rewind(snapshot);
+    assert_eq!(lake.as_slice(), b"user");
+    assert_eq!(lake.peek::<32>(), Some(&[0u8; 32]));
+}
+
+#[test]
+fn test_secure_wipes_above_old_high_water_mark() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    lake.alloc_slice_copy(&[0xEEu8; 48]);
+    lake.reset_to(48);
+    lake.alloc_slice_copy(&[0xAAu8; 4]);
+
+    // Turned on late: the 48 secret bytes from before still get wiped.
+    lake.set_secure(true);
+    lake.reset();
+    assert_eq!(lake.peek::<48>(), Some(&[0u8; 48]));
+}
+
+#[test]
+fn test_secure_marks_and_sandboxes_wipe() {
+    let mut lake: DynLake = DynLake::new(64);
+    lake.set_secure(true);
+    let token = lake.mark();
+    lake.alloc_slice_copy(&[0x11u8; 8]);
+    lake.reset_to_mark(token).unwrap();
+    assert_eq!(lake.peek::<8>(), Some(&[0u8; 8]));
+
+    {
+        let mut sandbox = lake.sandbox();
+        sandbox.view().alloc_slice_copy(&[0x22u8; 16]);
+    }
+    assert!(lake.is_empty());
+    assert_eq!(lake.peek::<16>(), Some(&[0u8; 16]));
+}
+
+#[test]
+fn test_secure_views_wipe_on_every_rollback() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    lake.set_secure(true);
+    let mut view = lake.split(64).unwrap();
+    assert!(view.is_secure());
+
+    let token = view.mark();
+    view.alloc_slice_copy(&[0x11u8; 8]);
+    view.reset_to_mark(token).unwrap();
+    assert_eq!(view.peek::<8>(), Some(&[0u8; 8]));
+
+    view.alloc_slice_copy(&[0x22u8; 16]);
+    view.reset_to(16);
+    assert_eq!(view.peek::<16>(), Some(&[0u8; 16]));
+
+    view.alloc_slice_copy(&[0x33u8; 32]);
+    view.reset();
+    assert_eq!(view.peek::<32>(), Some(&[0u8; 32]));
+}
+
+#[test]
+fn test_secure_reset_drops_tracked_values_before_wiping() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    lake.set_secure(true);
+    lake.alloc_tracked(String::from("session key"));
+    lake.reset();
+    assert_eq!(lake.peek::<64>(), Some(&[0u8; 64]));
+}
+
+#[test]
+fn test_lock_memory() {
+    let mut lake: Lake<4096> = Lake::<4096>::new();
+    lake.set_secure(true);
+    match lake.lock_memory() {
+        Ok(()) => {
+            assert!(lake.is_memory_locked());
+            assert!(lake.lock_memory().is_ok());
+            assert!(!lake.clone().is_memory_locked());
+        }
+        // Sandboxes and CI often have RLIMIT_MEMLOCK set to nothing.
+        Err(err) => {
+            assert!(matches!(err, LakeError::MemoryLock(_)));
+            assert!(!lake.is_memory_locked());
+        }
+    }
+}
+
+#[test]
+fn test_secure_wipes_bytes_a_failed_process_in_place_left_behind() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    lake.set_secure(true);
+    let failed = lake.process_in_place(|buf: &mut [u8]| {
+        buf[..4].copy_from_slice(b"KEY!");
+        Err::<usize, &str>("handshake aborted")
+    });
+    assert!(failed.is_err());
+    lake.reset();
+    assert_eq!(lake.peek::<4>(), Some(&[0u8; 4]));
+
+    let mut lake: DynLake = DynLake::new(64);
+    lake.set_secure(true);
+    let droplet = lake
+        .process_in_place(|buf: &mut [u8]| {
+            buf[..8].copy_from_slice(b"ab-KEY!!");
+            Ok::<usize, &str>(2)
+        })
+        .unwrap();
+    assert_eq!(droplet.d_as_slice(), b"ab");
+    lake.reset();
+    assert_eq!(lake.peek::<8>(), Some(&[0u8; 8]));
+}
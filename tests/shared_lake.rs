@@ -0,0 +1,89 @@
+use lake::{SharedDroplet, SharedLake};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+#[test]
+fn test_shared_lake_alloc_through_shared_ref() {
+    let lake: SharedLake<64> = SharedLake::<64>::new();
+    let mut a: SharedDroplet<[u8; 8]> = lake.alloc::<8>().unwrap();
+    let mut b: SharedDroplet<[u8]> = lake.alloc_dyn(16).unwrap();
+    a.fill(1);
+    b.fill(2);
+    assert_eq!(*a, [1u8; 8]);
+    assert_eq!(b.len(), 16);
+    assert_eq!(lake.used(), 24);
+    assert_eq!(lake.borrowers(), 2);
+}
+
+#[test]
+fn test_shared_lake_overflow() {
+    let lake: SharedLake<16> = SharedLake::<16>::new();
+    assert!(lake.alloc::<32>().is_none());
+    let _a = lake.alloc_dyn(10).unwrap();
+    assert!(lake.alloc_dyn(10).is_none());
+    assert!(lake.is_full());
+    assert_eq!(lake.borrowers(), 1);
+}
+
+#[test]
+fn test_shared_lake_concurrent_fill() {
+    const THREADS: usize = 8;
+    const PER_THREAD: usize = 100;
+    let mut lake: SharedLake<{ THREADS * PER_THREAD * 4 }> = SharedLake::new();
+
+    std::thread::scope(|scope| {
+        for t in 0..THREADS {
+            let lake = &lake;
+            scope.spawn(move || {
+                for _ in 0..PER_THREAD {
+                    let mut droplet = lake.alloc::<4>().unwrap();
+                    droplet.fill(t as u8 + 1);
+                }
+            });
+        }
+    });
+
+    assert!(lake.is_full());
+    assert_eq!(lake.borrowers(), 0);
+    let slice: &[u8] = lake.as_slice();
+    for chunk in slice.chunks(4) {
+        // Every droplet was written by exactly one thread.
+        assert!(chunk.iter().all(|&b| b == chunk[0] && b != 0));
+    }
+    for t in 1..=THREADS as u8 {
+        assert_eq!(slice.iter().filter(|&&b| b == t).count(), PER_THREAD * 4);
+    }
+}
+
+#[test]
+fn test_shared_lake_try_reset_refuses_while_borrowed() {
+    let lake: SharedLake<32> = SharedLake::<32>::new();
+    let droplet = lake.alloc::<8>().unwrap();
+    assert!(!lake.try_reset());
+    assert_eq!(lake.used(), 8);
+    drop(droplet);
+    assert!(lake.try_reset());
+    assert!(lake.is_empty());
+    assert_eq!(lake.generation(), 1);
+}
+
+#[test]
+fn test_shared_lake_reset_waits_for_borrowers() {
+    let lake: SharedLake<32> = SharedLake::<32>::new();
+    let released: AtomicBool = AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        let droplet = lake.alloc::<8>().unwrap();
+        let released = &released;
+        scope.spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            released.store(true, Ordering::SeqCst);
+            drop(droplet);
+        });
+        lake.reset();
+        assert!(released.load(Ordering::SeqCst));
+    });
+
+    assert!(lake.is_empty());
+    assert!(lake.alloc::<32>().is_some());
+}
@@ -0,0 +1,130 @@
+use lake::lake::{LakeError, LakeMeta, LakeSandboxExt, LakeSnapshot};
+use lake::lake_view::LakeView;
+use lake::{ChainedLake, DynLake, Lake};
+
+#[test]
+fn test_snapshot_records_generation_and_origin() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    lake.reset();
+    let _ = lake.alloc::<8>().unwrap();
+    let snap: LakeSnapshot = lake.snapshot();
+    assert_eq!(snap.offset, 8);
+    assert_eq!(snap.generation, 1);
+    assert_ne!(snap.origin, 0);
+}
+
+#[test]
+fn test_try_rewind_refuses_forward_jump() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let _ = lake.alloc::<16>().unwrap();
+    let ahead: LakeSnapshot = lake.snapshot();
+    lake.reset_to(16);
+
+    let err = lake.try_rewind(ahead).unwrap_err();
+    assert!(matches!(err, LakeError::InvalidSnapshot { offset: 16, current: 0 }));
+    assert_eq!(lake.used(), 0);
+}
+
+#[test]
+fn test_try_rewind_refuses_older_generation() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let _ = lake.alloc::<16>().unwrap();
+    let snap: LakeSnapshot = lake.snapshot();
+    lake.reset();
+    let _ = lake.alloc::<32>().unwrap();
+
+    let err = lake.try_rewind(snap).unwrap_err();
+    assert!(matches!(err, LakeError::StaleGeneration { expected: 0, found: 1 }));
+    assert_eq!(lake.used(), 32);
+}
+
+#[test]
+fn test_try_rewind_refuses_foreign_snapshot() {
+    let mut first: Lake<64> = Lake::<64>::new();
+    let mut second: DynLake = DynLake::new(64);
+    let snap: LakeSnapshot = first.snapshot();
+    let _ = first.alloc::<8>().unwrap();
+    let _ = second.alloc::<8>().unwrap();
+
+    assert!(matches!(second.try_rewind(snap), Err(LakeError::ForeignSnapshot { .. })));
+    assert_eq!(second.used(), 8);
+    first.try_rewind(snap).unwrap();
+    assert_eq!(first.used(), 0);
+}
+
+#[test]
+#[should_panic(expected = "stale generation")]
+fn test_rewind_panics_on_stale_snapshot() {
+    let mut lake: Lake<64> = Lake::<64>::new();
+    let snap: LakeSnapshot = lake.snapshot();
+    lake.reset();
+    lake.rewind(snap);
+}
+
+#[test]
+fn test_snapshot_guard_rewinds_on_drop() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let _ = lake.alloc::<8>().unwrap();
+    {
+        let mut guard = lake.snapshot_guard();
+        assert_eq!(guard.snapshot().offset, 8);
+        let _ = guard.alloc::<64>().unwrap();
+        assert_eq!(guard.used(), 72);
+    }
+    assert_eq!(lake.used(), 8);
+
+    let mut guard = lake.snapshot_guard();
+    let _ = guard.alloc::<16>().unwrap();
+    guard.keep();
+    assert_eq!(lake.used(), 24);
+}
+
+#[test]
+fn test_snapshot_guard_leaves_reset_lake_alone() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let _ = lake.alloc::<8>().unwrap();
+    {
+        let mut guard = lake.snapshot_guard();
+        guard.reset();
+        let _ = guard.alloc::<4>().unwrap();
+    }
+    assert_eq!(lake.used(), 4);
+    assert_eq!(lake.generation(), 1);
+}
+
+#[test]
+fn test_views_and_chains_validate_too() {
+    let mut parent: Lake<128> = Lake::<128>::new();
+    let mut left: LakeView<128> = parent.split(32).unwrap();
+    let mut right: LakeView<128> = parent.split(32).unwrap();
+    let left_snap: LakeSnapshot = left.snapshot();
+    let _ = left.alloc::<8>().unwrap();
+    assert!(matches!(right.try_rewind(left_snap), Err(LakeError::ForeignSnapshot { .. })));
+    left.try_rewind(left_snap).unwrap();
+    assert!(left.is_empty());
+
+    let mut chain: ChainedLake<16> = ChainedLake::<16>::new();
+    let snap: LakeSnapshot = chain.snapshot();
+    let _ = chain.alloc::<16>().unwrap();
+    let _ = chain.alloc::<16>().unwrap();
+    chain.try_rewind(snap).unwrap();
+    assert_eq!(chain.used(), 0);
+}
+
+#[test]
+fn test_views_at_offset_zero_are_lakes_of_their_own() {
+    let mut parent: Lake<128> = Lake::<128>::new();
+    let mut view: LakeView<128> = parent.split(64).unwrap();
+    let view_snap: LakeSnapshot = view.snapshot();
+    let mut inner: LakeView<128> = view.split(32).unwrap();
+    let inner_snap: LakeSnapshot = inner.snapshot();
+
+    assert!(matches!(parent.try_rewind(view_snap), Err(LakeError::ForeignSnapshot { .. })));
+    assert!(matches!(parent.try_rewind(inner_snap), Err(LakeError::ForeignSnapshot { .. })));
+    assert!(matches!(inner.try_rewind(view_snap), Err(LakeError::ForeignSnapshot { .. })));
+    assert_eq!(parent.used(), 64);
+
+    let lake: Lake<32> = Lake::<32>::new();
+    let mut copy: Lake<32> = lake.clone();
+    assert!(matches!(copy.try_rewind(lake.snapshot()), Err(LakeError::ForeignSnapshot { .. })));
+}
@@ -1,6 +1,6 @@
 use std::panic;
-use lake::thread_lake::{thread_lake_init, DEFAULT_SIZE};
-use lake::{with_lake, Lake};
+use lake::thread_lake::{thread_lake_init, DEFAULT_LAKE, DEFAULT_SIZE};
+use lake::{thread_lake, try_with_lake, with_lake, Lake};
 
 #[test]
 fn test_thread_lake_initialization_and_access() {
@@ -25,23 +25,56 @@ fn test_thread_lake_reuse_in_same_thread() {
 }
 
 #[test]
-fn test_thread_lake_panic_without_init() {
+fn test_thread_lake_lazy_init_without_init_call() {
     lake::thread_lake::THREAD_LAKE.with(|slot| {
         *slot.borrow_mut() = None;
     });
+    assert!(!DEFAULT_LAKE.is_initialized());
+
+    with_lake!(|lake: &mut Lake<DEFAULT_SIZE>| {
+        assert_eq!(lake.used(), 0);
+        let _ = lake.alloc::<4>().unwrap();
+    });
+
+    assert!(DEFAULT_LAKE.is_initialized());
+    assert_eq!(with_lake!(|lake: &mut Lake<DEFAULT_SIZE>| lake.used()), 4);
+}
+
+#[test]
+fn test_try_with_lake_does_not_panic_when_reentered() {
+    let outer: Option<Option<usize>> = try_with_lake!(|_outer: &mut Lake<DEFAULT_SIZE>| {
+        try_with_lake!(|inner: &mut Lake<DEFAULT_SIZE>| inner.used())
+    });
+    assert_eq!(outer, Some(None));
 
     let result: std::thread::Result<()> = panic::catch_unwind(|| {
-        with_lake!(|_lake| {
-            // ...
+        with_lake!(|_outer: &mut Lake<DEFAULT_SIZE>| {
+            with_lake!(|_inner: &mut Lake<DEFAULT_SIZE>| {});
         });
     });
-
     assert!(result.is_err());
-    if let Err(err) = result {
-        let msg: Option<&str> = err
-            .downcast_ref::<&'static str>()
-            .copied()
-            .or_else(|| err.downcast_ref::<String>().map(|s| s.as_str()));
-        assert!(msg.unwrap_or("").contains("Lake not initialized"));
-    }
+}
+
+thread_lake! {
+    static SCRATCH: 1024;
+    pub(crate) static JSON: 4 * 4096;
+}
+
+#[test]
+fn test_named_thread_lakes_are_independent() {
+    SCRATCH.with(|lake: &mut Lake<1024>| {
+        let _ = lake.alloc::<100>().unwrap();
+    });
+    JSON.with(|lake: &mut Lake<{ 4 * 4096 }>| {
+        assert_eq!(lake.capacity(), 4 * 4096);
+        assert!(lake.is_empty());
+    });
+    assert_eq!(SCRATCH.with(|lake| lake.used()), 100);
+
+    // Another thread sees its own, fresh lake.
+    let used: usize = std::thread::spawn(|| SCRATCH.with(|lake| lake.used())).join().unwrap();
+    assert_eq!(used, 0);
+
+    assert_eq!(SCRATCH.take().map(|lake| lake.used()), Some(100));
+    assert_eq!(SCRATCH.try_with(|lake| lake.used()), Some(0));
 }
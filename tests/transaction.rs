@@ -0,0 +1,144 @@
+use lake::lake::{LakeAllocatorExt, LakeMeta, LakeSandboxExt};
+use lake::mark::MarkToken;
+use lake::transaction::Transaction;
+use lake::{ChainedLake, DynLake, Lake};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+
+#[test]
+fn test_transaction_commits_on_ok() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let len: Result<usize, &str> = lake.transaction(|tx| {
+        let _ = tx.alloc::<16>().unwrap();
+        Ok(tx.used())
+    });
+    assert_eq!(len, Ok(16));
+    assert_eq!(lake.used(), 16);
+}
+
+#[test]
+fn test_transaction_rolls_back_on_err() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let _ = lake.alloc::<8>().unwrap();
+    let result: Result<(), &str> = lake.transaction(|tx| {
+        let _ = tx.alloc::<32>().unwrap();
+        Err("grammar alternative did not match")
+    });
+    assert_eq!(result, Err("grammar alternative did not match"));
+    assert_eq!(lake.used(), 8);
+}
+
+#[test]
+fn test_transaction_rolls_back_on_panic() {
+    let shared: Arc<()> = Arc::new(());
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let caught = catch_unwind(AssertUnwindSafe(|| {
+        let _: Result<(), ()> = lake.transaction(|tx| {
+            tx.alloc_tracked(shared.clone());
+            let _ = tx.alloc::<16>().unwrap();
+            panic!("parser blew up");
+        });
+    }));
+    assert!(caught.is_err());
+    assert!(lake.is_empty());
+    assert_eq!(Arc::strong_count(&shared), 1);
+}
+
+#[test]
+fn test_transaction_restores_marks() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let _ = lake.alloc::<8>().unwrap();
+    let outer: MarkToken = lake.mark();
+    let _ = lake.alloc::<8>().unwrap();
+
+    let result: Result<(), ()> = lake.transaction(|tx| {
+        tx.reset_to_mark(outer).unwrap();
+        let _ = tx.mark();
+        let _ = tx.mark();
+        Err(())
+    });
+    assert!(result.is_err());
+    assert_eq!(lake.used(), 16);
+    assert_eq!(lake.generation(), 0);
+    assert_eq!(lake.marks(), 1);
+    lake.reset_to_mark(outer).unwrap();
+    assert_eq!(lake.used(), 8);
+}
+
+#[test]
+fn test_rolled_back_reset_never_reuses_a_generation() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let _ = lake.alloc::<8>().unwrap();
+    let mut inside = None;
+
+    let result: Result<(), ()> = lake.transaction(|tx| {
+        tx.reset();
+        inside = Some(tx.alloc::<8>().unwrap());
+        Err(())
+    });
+    assert!(result.is_err());
+    assert_eq!(lake.used(), 8);
+    assert_eq!(lake.generation(), 2);
+
+    let droplet = inside.unwrap();
+    assert!(!droplet.is_valid());
+    lake.reset();
+    let _ = lake.alloc::<16>().unwrap();
+    assert!(!droplet.is_valid());
+}
+
+#[test]
+fn test_marks_from_rolled_back_transaction_stay_dead() {
+    let mut lake: DynLake = DynLake::new(64);
+    let mut leaked: Option<MarkToken> = None;
+    let _: Result<(), ()> = lake.transaction(|tx| {
+        leaked = Some(tx.mark());
+        Err(())
+    });
+    let fresh: MarkToken = lake.mark();
+    assert_eq!(leaked.unwrap().depth, fresh.depth);
+    assert!(lake.reset_to_mark(leaked.unwrap()).is_err());
+    lake.reset_to_mark(fresh).unwrap();
+}
+
+#[test]
+fn test_savepoints_nest() {
+    let mut lake: Lake<256> = Lake::<256>::new();
+    let result: Result<usize, ()> = lake.transaction(|tx| {
+        let _ = tx.alloc::<8>().unwrap();
+        {
+            let mut first: Transaction<'_, Lake<256>> = tx.savepoint();
+            let _ = first.alloc::<100>().unwrap();
+            first.rollback();
+        }
+        {
+            let mut second: Transaction<'_, Lake<256>> = tx.savepoint();
+            let _ = second.alloc::<16>().unwrap();
+            second.commit();
+        }
+        let inner: Result<(), ()> = tx.transaction(|inner| {
+            let _ = inner.alloc::<50>().unwrap();
+            Err(())
+        });
+        assert!(inner.is_err());
+        Ok(tx.used())
+    });
+    assert_eq!(result, Ok(24));
+    assert_eq!(lake.used(), 24);
+}
+
+#[test]
+fn test_committed_savepoint_rolls_back_with_outer() {
+    let mut lake: ChainedLake<16> = ChainedLake::<16>::new();
+    let result: Result<(), ()> = lake.transaction(|tx| {
+        let mut savepoint = tx.savepoint();
+        let _ = savepoint.alloc::<12>().unwrap();
+        let _ = savepoint.alloc::<12>().unwrap();
+        let grown: usize = savepoint.used();
+        savepoint.commit();
+        assert_eq!(tx.used(), grown);
+        Err(())
+    });
+    assert!(result.is_err());
+    assert_eq!(lake.used(), 0);
+}
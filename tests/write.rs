@@ -0,0 +1,84 @@
+use lake::droplet::Droplet;
+use lake::droplet_dyn::DropletDyn;
+use lake::lake::LakeAllocatorExt;
+use lake::small_lake::SmallLake;
+use lake::{DropletBase, Lake};
+use std::fmt::Write;
+use std::io;
+
+#[test]
+fn test_fmt_write_into_droplet() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let mut droplet: Droplet<32, Lake<128>> = lake.alloc::<32>().unwrap();
+    let status: &str = "OK";
+    write!(droplet, "HTTP/1.1 {} {status}\r\n", 200).unwrap();
+    let len: usize = droplet.d_offset();
+    assert_eq!(&droplet.d_as_slice()[..len], b"HTTP/1.1 200 OK\r\n");
+}
+
+#[test]
+fn test_fmt_write_overflow_is_an_error() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let mut droplet: Droplet<8, Lake<128>> = lake.alloc::<8>().unwrap();
+    droplet.write_str("1234").unwrap();
+    assert!(droplet.write_str("56789").is_err());
+    // All-or-nothing: the failed piece left nothing behind.
+    assert_eq!(droplet.d_offset(), 4);
+    assert!(write!(droplet, "{}", 5678).is_ok());
+}
+
+#[test]
+fn test_io_write_into_droplet_dyn() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let mut droplet: DropletDyn<128> = lake.alloc_dyn(16).unwrap();
+    io::Write::write_all(&mut droplet, b"content-length: ").unwrap();
+    assert_eq!(droplet.d_remaining(), 0);
+
+    let err: io::Error = io::Write::write_all(&mut droplet, b"42").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_io_write_partial() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let mut droplet = lake.alloc_dyn_ref(4).unwrap();
+    assert_eq!(io::Write::write(&mut droplet, b"abcdef").unwrap(), 4);
+    assert_eq!(&*droplet, b"abcd");
+    assert_eq!(io::Write::write(&mut droplet, b"").unwrap(), 0);
+    assert_eq!(io::Write::write(&mut droplet, b"x").unwrap_err().kind(), io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_small_lake_streaming_writes() {
+    let mut small: SmallLake<16> = SmallLake::<16>::build();
+    write!(small, "{}-{}", 12, 34).unwrap();
+    assert_eq!(small.len(), 5);
+    assert_eq!(unsafe { small.as_slice() }, b"12-34");
+
+    // Streaming writes stop at the end instead of wrapping over the start.
+    let err: io::Error = io::Write::write_all(&mut small, b"0123456789abcdef").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    assert_eq!(small.len(), 16);
+    assert_eq!(&unsafe { small.as_slice() }[..5], b"12-34");
+    assert!(small.write_str("!").is_err());
+
+    small.reset_pos();
+    assert!(small.write_str("fresh").is_ok());
+}
+
+#[test]
+fn test_write_num_str_then_streaming_writes() {
+    let mut lake: Lake<128> = Lake::<128>::new();
+    let mut droplet: Droplet<8, Lake<128>> = lake.alloc::<8>().unwrap();
+    droplet.write_num_str(42);
+    assert_eq!(droplet.d_offset(), 2);
+    write!(droplet, "-{}", 7).unwrap();
+    assert_eq!(&droplet.d_as_slice()[..droplet.d_offset()], b"42-7");
+
+    let mut small: Droplet<4, Lake<128>> = lake.alloc::<4>().unwrap();
+    small.write_num_str(123);
+    assert_eq!(io::Write::write(&mut small, b"XY").unwrap(), 1);
+    assert_eq!(small.d_as_slice(), b"123X");
+    assert_eq!(small.d_remaining(), 0);
+    assert_eq!(io::Write::write(&mut small, b"Z").unwrap_err().kind(), io::ErrorKind::WriteZero);
+}
@@ -0,0 +1,38 @@
+use lake::lake::memory::{CopyPath, LakeTools};
+
+fn check_all_lengths(path: CopyPath) {
+    let src: Vec<u8> = (0..2100u32).map(|i| (i * 7 + 3) as u8).collect();
+    for len in (0..200).chain([255, 256, 1023, 1024, 1025, 2048, 2100]) {
+        // Odd offsets make sure unaligned heads and tails are handled.
+        let mut dst: Vec<u8> = vec![0xAA; len + 3];
+        unsafe { LakeTools::write_to_using(path, dst.as_mut_ptr().add(1), src.as_ptr().add(0), len) };
+        assert_eq!(&dst[1..len + 1], &src[..len], "{path:?}, len {len}");
+        assert_eq!(dst[0], 0xAA);
+        assert_eq!(dst[len + 1], 0xAA, "{path:?} wrote past the end, len {len}");
+    }
+}
+
+#[test]
+fn test_every_copy_path_copies_exactly() {
+    for path in [CopyPath::Scalar, CopyPath::Sse2, CopyPath::Avx2, CopyPath::Avx512] {
+        check_all_lengths(path);
+    }
+}
+
+#[test]
+fn test_detected_copy_path_is_stable() {
+    let path: CopyPath = LakeTools::copy_path();
+    assert_eq!(LakeTools::copy_path(), path);
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    assert_eq!(path, CopyPath::Scalar);
+    #[cfg(target_arch = "x86_64")]
+    assert!(path >= CopyPath::Sse2);
+}
+
+#[test]
+fn test_write_to_default_path() {
+    let src: [u8; 1024] = [9u8; 1024];
+    let mut dst: [u8; 1024] = [0u8; 1024];
+    unsafe { LakeTools::write_to(dst.as_mut_ptr(), src.as_ptr(), src.len()) };
+    assert_eq!(dst, src);
+}